@@ -1,6 +1,6 @@
 //! Wrappers and extension traits for anyhow support
 
-use crate::{DynHttpError, HttpError, IntoHttpErrorResponse};
+use crate::{DynHttpError, ErrorMapper, HttpError, IntoHttpErrorResponse};
 use http::StatusCode;
 use std::{error::Error, fmt::Display};
 
@@ -15,12 +15,88 @@ pub struct AnyhowHttpError {
     error: anyhow::Error,
     /// The response status code
     status: StatusCode,
+    /// Public reason to use instead of the default
+    reason: Option<String>,
+    /// Attached key-value pairs included in the error details
+    #[cfg(feature = "json")]
+    attachments: serde_json::Map<String, serde_json::Value>,
+}
+
+impl AnyhowHttpError {
+    /// Creates a new wrapper around the anyhow error
+    fn new(error: anyhow::Error, status: StatusCode, reason: Option<String>) -> Self {
+        Self {
+            error,
+            status,
+            reason,
+            #[cfg(feature = "json")]
+            attachments: Default::default(),
+        }
+    }
+
+    /// Creates a wrapper using the status from the [ErrorMapper], falling
+    /// back to the default status (See [ErrorConfig::default_status](crate::ErrorConfig::default_status))
+    pub(crate) fn from_anyhow(error: anyhow::Error) -> Self {
+        match ErrorMapper::map(error.as_ref()) {
+            Some(mapping) => Self::new(error, mapping.status, mapping.reason),
+            // Give the error a default status
+            None => Self::new(error, crate::config::default_status(), None),
+        }
+    }
+
+    /// The underlying anyhow error
+    pub fn error(&self) -> &anyhow::Error {
+        &self.error
+    }
+
+    /// The backtrace captured by the anyhow error
+    pub fn backtrace(&self) -> &std::backtrace::Backtrace {
+        self.error.backtrace()
+    }
+
+    /// Attaches a key-value pair to the error which is included in the
+    /// error details by structured formatters, values that fail to
+    /// serialize are ignored
+    ///
+    /// ```
+    /// use axum_dyn_error::{AnyhowStatusExt, StatusCode};
+    ///
+    /// let error = anyhow::anyhow!("Order could not be fulfilled")
+    ///     .status(StatusCode::CONFLICT)
+    ///     .attach("order_id", 42);
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn attach(mut self, key: impl Into<String>, value: impl serde::Serialize) -> Self {
+        if let Ok(value) = serde_json::to_value(value) {
+            self.attachments.insert(key.into(), value);
+        }
+        self
+    }
+
+    /// Wraps the underlying anyhow error with additional context while
+    /// keeping the status and reason (See [anyhow::Error::context])
+    pub fn context<C>(self, context: C) -> Self
+    where
+        C: Display + Send + Sync + 'static,
+    {
+        Self {
+            error: self.error.context(context),
+            ..self
+        }
+    }
 }
 
 impl Error for AnyhowHttpError {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
         self.error.source()
     }
+
+    /// Provides the values from the anyhow error such as its backtrace
+    #[cfg(feature = "provide")]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        let error: &(dyn Error + 'static) = self.error.as_ref();
+        error.provide(request)
+    }
 }
 
 impl Display for AnyhowHttpError {
@@ -33,32 +109,63 @@ impl HttpError for AnyhowHttpError {
     #[cfg(feature = "log")]
     fn log(&self) {
         // Anyhow errors contain a stacktrace so only the debug variant is used
-        log::error!("{:?}", self.error);
+        #[cfg(feature = "json")]
+        if !self.attachments.is_empty() {
+            log::error!(
+                "{:?} {}{}",
+                self.error,
+                serde_json::Value::Object(self.attachments.clone()),
+                crate::location::log_suffix()
+            );
+            return;
+        }
+
+        log::error!("{:?}{}", self.error, crate::location::log_suffix());
     }
 
     fn status(&self) -> StatusCode {
         self.status
     }
 
-    #[cfg(feature = "hide-anyhow")]
     fn reason(&self) -> String {
+        if let Some(reason) = &self.reason {
+            return reason.clone();
+        }
+
         // Anyhow errors use a generic message
-        "Server error".to_string()
+        #[cfg(feature = "hide-anyhow")]
+        {
+            "Server error".to_string()
+        }
+
+        #[cfg(not(feature = "hide-anyhow"))]
+        {
+            self.to_string()
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        if self.attachments.is_empty() {
+            return None;
+        }
+
+        Some(serde_json::Value::Object(self.attachments.clone()))
     }
 }
 
 /// Allow conversion from anyhow errors into [DynHttpError] by wrapping
 /// them with [AnyhowHttpError]
+///
+/// The [ErrorMapper] is consulted for the status of the error, falling
+/// back to the default status (See [ErrorConfig::default_status](crate::ErrorConfig::default_status))
 impl<I> From<anyhow::Error> for DynHttpError<I>
 where
     I: IntoHttpErrorResponse,
 {
+    #[track_caller]
     fn from(value: anyhow::Error) -> Self {
-        value
-            // Give the error a default status
-            .status(StatusCode::INTERNAL_SERVER_ERROR)
-            // Convert into the dyn error type
-            .into()
+        AnyhowHttpError::from_anyhow(value).into()
     }
 }
 
@@ -66,13 +173,58 @@ where
 pub trait AnyhowStatusExt {
     /// Add an additional status code to the anyhow error response
     fn status(self, status: StatusCode) -> AnyhowHttpError;
+
+    /// Add an additional status code and a public message to the anyhow
+    /// error response, the message is used as the response reason while
+    /// the full error is still logged
+    fn status_msg(self, status: StatusCode, message: impl Into<String>) -> AnyhowHttpError;
 }
 
 impl AnyhowStatusExt for anyhow::Error {
     fn status(self, status: StatusCode) -> AnyhowHttpError {
-        AnyhowHttpError {
-            error: self,
-            status,
-        }
+        AnyhowHttpError::new(self, status, None)
+    }
+
+    fn status_msg(self, status: StatusCode, message: impl Into<String>) -> AnyhowHttpError {
+        AnyhowHttpError::new(self, status, Some(message.into()))
     }
 }
+
+#[doc(hidden)]
+pub use ::anyhow::anyhow as __anyhow;
+
+/// Creates an [AnyhowHttpError] with the provided status using the same
+/// arguments as [anyhow::anyhow!]
+///
+/// ```
+/// use axum_dyn_error::{http_anyhow, StatusCode};
+///
+/// let error = http_anyhow!(StatusCode::CONFLICT, "User {} already exists", 1)
+///     .context("Failed to create user");
+/// ```
+#[macro_export]
+macro_rules! http_anyhow {
+    ($status:expr, $($arg:tt)+) => {
+        $crate::anyhow::AnyhowStatusExt::status($crate::anyhow::__anyhow!($($arg)+), $status)
+    };
+}
+
+/// Returns early with an [AnyhowHttpError] with the provided status, the
+/// status aware equivalent of [anyhow::bail!] for migrating existing code
+///
+/// ```
+/// use axum_dyn_error::{http_anyhow_bail, HttpResult, StatusCode};
+///
+/// fn create_user(exists: bool) -> HttpResult<()> {
+///     if exists {
+///         http_anyhow_bail!(StatusCode::CONFLICT, "User already exists");
+///     }
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! http_anyhow_bail {
+    ($status:expr, $($arg:tt)+) => {
+        return ::std::result::Result::Err($crate::http_anyhow!($status, $($arg)+).into())
+    };
+}
@@ -0,0 +1,199 @@
+//! Support for the errors of archive uploads (`zip`)
+//!
+//! Malformed archives are 400 Bad Request errors and archives that can't
+//! be extracted (unsupported features, missing entries or wrong passwords)
+//! are 422 Unprocessable Entity errors. Errors for a specific entry can be
+//! wrapped in an [ArchiveEntryError] to include the entry name in the
+//! details
+//!
+//! Entries with paths escaping the extraction directory (i.e `../`) are
+//! rejected with [UnsafeArchiveEntry] which is flagged as security relevant
+//! so that path traversal attempts are sent to the audit sinks
+//!
+//! ```
+//! use std::io::Cursor;
+//!
+//! use axum_dyn_error::{ArchiveEntryError, HttpResult, UnsafeArchiveEntry};
+//! use zip::ZipArchive;
+//!
+//! pub async fn upload(body: Vec<u8>) -> HttpResult<String> {
+//!     let mut archive = ZipArchive::new(Cursor::new(body))?;
+//!     let mut paths = Vec::new();
+//!     for index in 0..archive.len() {
+//!         let entry = archive.by_index(index)?;
+//!         paths.push(UnsafeArchiveEntry::check(&entry)?);
+//!     }
+//!
+//!     archive
+//!         .by_name("manifest.json")
+//!         .map_err(|error| ArchiveEntryError::new("manifest.json", error))?;
+//!
+//!     Ok(format!("Extracted {} entries", paths.len()))
+//! }
+//! ```
+
+use std::error::Error;
+use std::fmt::Display;
+use std::io::Read;
+use std::path::PathBuf;
+
+use http::StatusCode;
+use zip::{read::ZipFile, result::ZipError};
+
+use crate::HttpError;
+
+/// Archive failures keep their meaning for the client, IO failures while
+/// reading the archive are server errors with a generic reason
+impl HttpError for ZipError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_client_error() {
+            log::debug!("{self}");
+        } else {
+            log::error!("{self}{}", crate::location::log_suffix());
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            ZipError::InvalidArchive(_) => StatusCode::BAD_REQUEST,
+            ZipError::UnsupportedArchive(_)
+            | ZipError::FileNotFound
+            | ZipError::InvalidPassword => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn reason(&self) -> String {
+        match self {
+            ZipError::InvalidArchive(_) => "Invalid archive",
+            ZipError::UnsupportedArchive(_) => "Unsupported archive",
+            ZipError::FileNotFound => "Archive entry not found",
+            ZipError::InvalidPassword => "Invalid archive password",
+            _ => "Server error",
+        }
+        .to_string()
+    }
+}
+
+/// [ZipError] for a specific entry of an archive, the name of the entry
+/// is included in the details
+#[derive(Debug)]
+pub struct ArchiveEntryError {
+    /// Name of the entry
+    entry: String,
+    /// The error for the entry
+    error: ZipError,
+}
+
+impl ArchiveEntryError {
+    /// Creates a new error for the entry with the provided name
+    pub fn new(entry: impl Into<String>, error: ZipError) -> Self {
+        Self {
+            entry: entry.into(),
+            error,
+        }
+    }
+
+    /// Name of the entry
+    pub fn entry(&self) -> &str {
+        &self.entry
+    }
+}
+
+impl Display for ArchiveEntryError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} (entry {})", self.error, self.entry)
+    }
+}
+
+impl Error for ArchiveEntryError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl HttpError for ArchiveEntryError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_client_error() {
+            log::debug!("{self}");
+        } else {
+            log::error!("{self}{}", crate::location::log_suffix());
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        self.error.status()
+    }
+
+    fn reason(&self) -> String {
+        self.error.reason()
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({ "entry": self.entry }))
+    }
+}
+
+/// Error for archive entries with a path that escapes the extraction
+/// directory (422 Unprocessable Entity), the entry name is included in the
+/// details and the error is security relevant
+#[derive(Debug, Clone)]
+pub struct UnsafeArchiveEntry {
+    /// Name of the entry
+    entry: String,
+}
+
+impl UnsafeArchiveEntry {
+    /// Creates a new error for the entry with the provided name
+    pub fn new(entry: impl Into<String>) -> Self {
+        Self {
+            entry: entry.into(),
+        }
+    }
+
+    /// Checks the path of the entry, returning the relative path the entry
+    /// can be safely extracted to
+    pub fn check<R: Read>(file: &ZipFile<'_, R>) -> Result<PathBuf, Self> {
+        file.enclosed_name().ok_or_else(|| Self::new(file.name()))
+    }
+
+    /// Name of the entry
+    pub fn entry(&self) -> &str {
+        &self.entry
+    }
+}
+
+impl Display for UnsafeArchiveEntry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Unsafe archive entry path: {}", self.entry)
+    }
+}
+
+impl Error for UnsafeArchiveEntry {}
+
+impl HttpError for UnsafeArchiveEntry {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        log::warn!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::UNPROCESSABLE_ENTITY
+    }
+
+    fn reason(&self) -> String {
+        "Unsafe archive entry".to_string()
+    }
+
+    fn security_relevant(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({ "entry": self.entry }))
+    }
+}
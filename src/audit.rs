@@ -0,0 +1,148 @@
+//! Audit trail for security relevant errors such as authentication
+//! failures, kept separate from the normal logs
+
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use http::{request::Parts, StatusCode};
+
+use crate::{ErrorKind, HttpError};
+
+/// Function for resolving the actor responsible for a request
+type ActorResolver = Arc<dyn Fn(&Parts) -> Option<String> + Send + Sync>;
+
+/// Registered audit sinks
+static SINKS: RwLock<Vec<Arc<dyn AuditSink>>> = RwLock::new(Vec::new());
+
+/// Registered actor resolver
+static ACTOR_RESOLVER: RwLock<Option<ActorResolver>> = RwLock::new(None);
+
+/// Structured audit record for a security relevant error
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AuditRecord {
+    /// When the error occurred
+    pub timestamp: SystemTime,
+    /// The actor responsible for the request if known (See [Audit::actor_resolver])
+    pub actor: Option<String>,
+    /// The HTTP status code of the error
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::describe::serialize_status")
+    )]
+    pub status: StatusCode,
+    /// The broad category of the error
+    pub kind: ErrorKind,
+    /// The full type name of the underlying error
+    pub type_name: String,
+    /// The machine readable error code if the error has one
+    pub code: Option<String>,
+    /// The full error message, this is not the public reason
+    pub message: String,
+    /// The method of the request if known
+    pub method: Option<String>,
+    /// The path of the request if known
+    pub path: Option<String>,
+    /// The ID of the request if known
+    pub request_id: Option<String>,
+}
+
+impl AuditRecord {
+    /// Creates an audit record for the provided error and optional
+    /// request parts
+    pub fn from_error(error: &dyn HttpError, parts: Option<&Parts>) -> Self {
+        let actor = parts.and_then(|parts| {
+            let resolver = ACTOR_RESOLVER
+                .read()
+                .unwrap_or_else(|err| err.into_inner())
+                .clone()?;
+            resolver(parts)
+        });
+
+        Self {
+            timestamp: SystemTime::now(),
+            actor,
+            status: error.status(),
+            kind: error.kind(),
+            type_name: error.type_name().to_string(),
+            code: error.code().map(str::to_string),
+            message: error.to_string(),
+            method: parts.map(|parts| parts.method.to_string()),
+            path: parts.map(|parts| parts.uri.path().to_string()),
+            request_id: parts
+                .and_then(|parts| parts.headers.get("x-request-id"))
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+        }
+    }
+}
+
+/// Destination for [AuditRecord]s such as a tamper-evident audit log
+pub trait AuditSink: Send + Sync + 'static {
+    /// Records the provided audit record
+    fn record(&self, record: &AuditRecord);
+}
+
+impl<F> AuditSink for F
+where
+    F: Fn(&AuditRecord) + Send + Sync + 'static,
+{
+    fn record(&self, record: &AuditRecord) {
+        self(record)
+    }
+}
+
+/// Registry of [AuditSink]s that receive a record for every security
+/// relevant error (See [HttpError::security_relevant]) converted into
+/// a response
+pub struct Audit;
+
+impl Audit {
+    /// Registers a sink that will receive audit records
+    pub fn register<S: AuditSink>(sink: S) {
+        SINKS
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(Arc::new(sink));
+    }
+
+    /// Sets the function used to resolve the actor responsible for a
+    /// request from its parts (i.e from an authorization header), the
+    /// parts are only available with the `tower` feature and the
+    /// [RequestContextLayer](crate::context::RequestContextLayer)
+    pub fn actor_resolver<F>(resolver: F)
+    where
+        F: Fn(&Parts) -> Option<String> + Send + Sync + 'static,
+    {
+        *ACTOR_RESOLVER
+            .write()
+            .unwrap_or_else(|err| err.into_inner()) = Some(Arc::new(resolver));
+    }
+
+    /// Removes all the registered sinks and the actor resolver
+    pub fn clear() {
+        SINKS.write().unwrap_or_else(|err| err.into_inner()).clear();
+        *ACTOR_RESOLVER
+            .write()
+            .unwrap_or_else(|err| err.into_inner()) = None;
+    }
+
+    /// Sends an audit record for the error to the registered sinks if
+    /// the error is security relevant
+    #[cfg(feature = "axum")]
+    pub(crate) fn emit(error: &dyn HttpError, parts: Option<&Parts>) {
+        if !error.security_relevant() {
+            return;
+        }
+
+        let sinks = SINKS.read().unwrap_or_else(|err| err.into_inner()).clone();
+        if sinks.is_empty() {
+            return;
+        }
+
+        let record = AuditRecord::from_error(error, parts);
+        for sink in sinks {
+            sink.record(&record);
+        }
+    }
+}
@@ -0,0 +1,235 @@
+//! Partial success responses for batch endpoints, reporting the errors
+//! of individual items instead of failing the whole request
+
+use std::marker::PhantomData;
+
+use axum_core::response::{IntoResponse, Response};
+use http::StatusCode;
+use serde::Serialize;
+
+use crate::json::json_response;
+use crate::{
+    apply_config, DynHttpError, ErrorBody, ErrorConfig, IntoHttpErrorResponse, TextErrorResponse,
+};
+
+/// How the results of a batch are rendered
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BatchMode {
+    /// 207 Multi-Status response with a `results` array containing the
+    /// status and the data or error of each item
+    #[default]
+    MultiStatus,
+    /// 200 OK response with a `results` array containing the data of the
+    /// successful items and an `errors` array containing the errors of
+    /// the failed items
+    ErrorsArray,
+}
+
+/// Results of a batch operation pairing the successful items with the
+/// errors of the failed items, errors are rendered using [ErrorBody]
+///
+/// ```
+/// use axum_dyn_error::{BatchResult, DynHttpError};
+///
+/// pub async fn delete_users() -> BatchResult<u32> {
+///     [1, 2, 3]
+///         .into_iter()
+///         .map(|id| match id {
+///             2 => Err(DynHttpError::not_found("User not found")),
+///             id => Ok(id),
+///         })
+///         .collect()
+/// }
+/// ```
+pub struct BatchResult<T, I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// The results of each item in order
+    items: Vec<Result<T, DynHttpError<I>>>,
+    /// How the results are rendered
+    mode: BatchMode,
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<T, I: IntoHttpErrorResponse> BatchResult<T, I> {
+    /// Creates a new empty batch result
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            mode: BatchMode::default(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Sets how the results are rendered
+    pub fn mode(mut self, mode: BatchMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    /// Adds the result of an item
+    pub fn push<E: Into<DynHttpError<I>>>(&mut self, result: Result<T, E>) {
+        self.items.push(result.map_err(Into::into));
+    }
+
+    /// Adds a successful item
+    pub fn success(&mut self, value: T) {
+        self.items.push(Ok(value));
+    }
+
+    /// Adds a failed item
+    pub fn failure(&mut self, error: impl Into<DynHttpError<I>>) {
+        self.items.push(Err(error.into()));
+    }
+
+    /// The results of each item in order
+    pub fn items(&self) -> &[Result<T, DynHttpError<I>>] {
+        &self.items
+    }
+
+    /// Number of successful items
+    pub fn successes(&self) -> usize {
+        self.items.iter().filter(|item| item.is_ok()).count()
+    }
+
+    /// Number of failed items
+    pub fn failures(&self) -> usize {
+        self.items.iter().filter(|item| item.is_err()).count()
+    }
+}
+
+impl<T, I: IntoHttpErrorResponse> Default for BatchResult<T, I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T, E, I> FromIterator<Result<T, E>> for BatchResult<T, I>
+where
+    E: Into<DynHttpError<I>>,
+    I: IntoHttpErrorResponse,
+{
+    fn from_iter<It: IntoIterator<Item = Result<T, E>>>(iter: It) -> Self {
+        let mut result = Self::new();
+        iter.into_iter().for_each(|item| result.push(item));
+        result
+    }
+}
+
+/// Result of an item in a [BatchMode::MultiStatus] response
+#[derive(Serialize)]
+struct MultiStatusItem<T> {
+    /// Index of the item in the batch
+    index: usize,
+    /// Status of the item
+    status: u16,
+    /// Data of the successful item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<T>,
+    /// Error of the failed item
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<ErrorBody>,
+}
+
+/// Successful item in a [BatchMode::ErrorsArray] response
+#[derive(Serialize)]
+struct SuccessItem<T> {
+    /// Index of the item in the batch
+    index: usize,
+    /// Data of the item
+    data: T,
+}
+
+/// Failed item in a [BatchMode::ErrorsArray] response
+#[derive(Serialize)]
+struct ErrorItem {
+    /// Index of the item in the batch
+    index: usize,
+    /// Error of the item
+    error: ErrorBody,
+}
+
+/// Body of a [BatchMode::MultiStatus] response
+#[derive(Serialize)]
+struct MultiStatusBody<T> {
+    /// Results of each item
+    results: Vec<MultiStatusItem<T>>,
+}
+
+/// Body of a [BatchMode::ErrorsArray] response
+#[derive(Serialize)]
+struct ErrorsArrayBody<T> {
+    /// The successful items
+    results: Vec<SuccessItem<T>>,
+    /// The failed items
+    errors: Vec<ErrorItem>,
+}
+
+/// Logs the error of a failed item and creates its body with the
+/// configured rewrites applied
+fn error_body<I: IntoHttpErrorResponse>(error: DynHttpError<I>, config: &ErrorConfig) -> ErrorBody {
+    #[cfg(all(feature = "log", feature = "track-caller"))]
+    let location = error.location;
+    let error = error.inner.into_boxed();
+
+    #[cfg(all(feature = "log", feature = "track-caller"))]
+    crate::location::with_location(location, || error.log());
+    #[cfg(all(feature = "log", not(feature = "track-caller")))]
+    error.log();
+
+    let error = apply_config(error, config);
+
+    // The item errors are nested within the batch body
+    let mut body = ErrorBody::from_error(error.as_ref());
+    body.schema_version = None;
+    body
+}
+
+impl<T: Serialize, I: IntoHttpErrorResponse> IntoResponse for BatchResult<T, I> {
+    fn into_response(self) -> Response {
+        let config = ErrorConfig::current();
+
+        match self.mode {
+            BatchMode::MultiStatus => {
+                let results = self
+                    .items
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, item)| match item {
+                        Ok(data) => MultiStatusItem {
+                            index,
+                            status: StatusCode::OK.as_u16(),
+                            data: Some(data),
+                            error: None,
+                        },
+                        Err(error) => {
+                            let error = error_body(error, &config);
+                            MultiStatusItem {
+                                index,
+                                status: error.status,
+                                data: None,
+                                error: Some(error),
+                            }
+                        }
+                    })
+                    .collect();
+
+                json_response(StatusCode::MULTI_STATUS, &MultiStatusBody { results })
+            }
+            BatchMode::ErrorsArray => {
+                let mut results = Vec::new();
+                let mut errors = Vec::new();
+                for (index, item) in self.items.into_iter().enumerate() {
+                    match item {
+                        Ok(data) => results.push(SuccessItem { index, data }),
+                        Err(error) => errors.push(ErrorItem {
+                            index,
+                            error: error_body(error, &config),
+                        }),
+                    }
+                }
+
+                json_response(StatusCode::OK, &ErrorsArrayBody { results, errors })
+            }
+        }
+    }
+}
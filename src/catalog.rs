@@ -0,0 +1,320 @@
+//! Catalog of the errors an application can respond with, used for
+//! documenting the error codes of an API
+
+use std::fmt::Write;
+use std::sync::RwLock;
+
+use http::StatusCode;
+
+use crate::ErrorKind;
+
+/// Registered catalog entries
+static ENTRIES: RwLock<Vec<CatalogEntry>> = RwLock::new(Vec::new());
+
+/// Entry in the [ErrorCatalog] describing an error
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CatalogEntry {
+    /// The name of the error type
+    pub name: &'static str,
+    /// The HTTP status code of the error
+    pub status: u16,
+    /// The machine readable error code
+    pub code: &'static str,
+    /// The message of the error
+    pub message: &'static str,
+}
+
+impl CatalogEntry {
+    /// The HTTP status code of the error
+    pub fn status(&self) -> StatusCode {
+        crate::parse_error_status(self.status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+}
+
+/// Error types that have a [CatalogEntry], implemented by the
+/// types created using [define_http_errors!](crate::define_http_errors)
+pub trait CatalogError {
+    /// The catalog entry for the error type
+    const ENTRY: CatalogEntry;
+}
+
+/// Registry of the errors an application can respond with
+pub struct ErrorCatalog;
+
+impl ErrorCatalog {
+    /// Registers the provided entry, entries with a code that has already
+    /// been registered replace the existing entry
+    pub fn register(entry: CatalogEntry) {
+        let mut entries = ENTRIES.write().unwrap_or_else(|err| err.into_inner());
+        match entries
+            .iter_mut()
+            .find(|existing| existing.code == entry.code)
+        {
+            Some(existing) => *existing = entry,
+            None => entries.push(entry),
+        }
+    }
+
+    /// Registers the entry for the error type `E`
+    pub fn register_type<E: CatalogError>() {
+        Self::register(E::ENTRY)
+    }
+
+    /// Obtains a copy of all the registered entries
+    pub fn entries() -> Vec<CatalogEntry> {
+        ENTRIES
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    /// Finds the registered entry with the provided code
+    pub fn find(code: &str) -> Option<CatalogEntry> {
+        ENTRIES
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .iter()
+            .find(|entry| entry.code == code)
+            .cloned()
+    }
+
+    /// Creates TypeScript type definitions for the registered errors for
+    /// generating client SDKs: an `ErrorCode` union of the codes, the
+    /// `ErrorBody` interface of JSON error responses (schema version 1)
+    /// and an interface for each error narrowing its status and code
+    ///
+    /// ```
+    /// use axum_dyn_error::{CatalogEntry, ErrorCatalog};
+    ///
+    /// ErrorCatalog::register(CatalogEntry {
+    ///     name: "UserNotFound",
+    ///     status: 404,
+    ///     code: "USER_NOT_FOUND",
+    ///     message: "User not found",
+    /// });
+    ///
+    /// let definitions = ErrorCatalog::to_typescript();
+    /// assert!(definitions.contains("export interface UserNotFound extends ErrorBody"));
+    /// ```
+    pub fn to_typescript() -> String {
+        let entries = Self::entries();
+        let mut output = String::new();
+
+        output.push_str("/** Machine readable codes of the errors the API responds with */\n");
+        output.push_str("export type ErrorCode =");
+        if entries.is_empty() {
+            output.push_str(" never");
+        }
+        for entry in &entries {
+            _ = write!(output, "\n  | {}", typescript_string(entry.code));
+        }
+        output.push_str(";\n\n");
+
+        output.push_str(concat!(
+            "/** Body of JSON error responses */\n",
+            "export interface ErrorBody {\n",
+            "  status: number;\n",
+            "  code?: ErrorCode;\n",
+            "  kind: string;\n",
+            "  reason: string;\n",
+            "  help?: string;\n",
+            "  remediation?: string;\n",
+            "  details?: unknown;\n",
+            "  truncated?: boolean;\n",
+            "  details_count?: number;\n",
+            "}\n",
+        ));
+
+        for entry in &entries {
+            _ = write!(
+                output,
+                "\n/** {} */\nexport interface {} extends ErrorBody {{\n  status: {};\n  code: {};\n  kind: {};\n}}\n",
+                entry.message.replace("*/", "*\\/"),
+                entry.name,
+                entry.status,
+                typescript_string(entry.code),
+                typescript_string(ErrorKind::from_status(entry.status()).as_str()),
+            );
+        }
+
+        output
+    }
+
+    /// Creates the OpenAPI `components` for the registered errors for
+    /// generating client SDKs: an `ErrorCode` enum schema, the `ErrorBody`
+    /// schema of JSON error responses (schema version 1), a schema for each
+    /// error narrowing its status and code and a response for each error
+    /// that can be referenced by operations
+    #[cfg(feature = "json")]
+    pub fn to_openapi_components() -> serde_json::Value {
+        use serde_json::{json, Map, Value};
+
+        let entries = Self::entries();
+        let mut schemas = Map::new();
+        let mut responses = Map::new();
+
+        schemas.insert(
+            "ErrorCode".to_string(),
+            json!({
+                "type": "string",
+                "enum": entries.iter().map(|entry| entry.code).collect::<Vec<_>>(),
+            }),
+        );
+        schemas.insert(
+            "ErrorBody".to_string(),
+            json!({
+                "type": "object",
+                "required": ["status", "kind", "reason"],
+                "properties": {
+                    "status": { "type": "integer" },
+                    "code": { "$ref": "#/components/schemas/ErrorCode" },
+                    "kind": { "type": "string" },
+                    "reason": { "type": "string" },
+                    "help": { "type": "string" },
+                    "remediation": { "type": "string" },
+                    "details": {},
+                    "truncated": { "type": "boolean" },
+                    "details_count": { "type": "integer" },
+                },
+            }),
+        );
+
+        for entry in &entries {
+            let reference = format!("#/components/schemas/{}", entry.name);
+            schemas.insert(
+                entry.name.to_string(),
+                json!({
+                    "allOf": [
+                        { "$ref": "#/components/schemas/ErrorBody" },
+                        {
+                            "type": "object",
+                            "required": ["code"],
+                            "properties": {
+                                "status": { "type": "integer", "enum": [entry.status] },
+                                "code": { "type": "string", "enum": [entry.code] },
+                                "reason": { "type": "string", "example": entry.message },
+                            },
+                        },
+                    ],
+                }),
+            );
+            responses.insert(
+                entry.name.to_string(),
+                json!({
+                    "description": entry.message,
+                    "content": {
+                        "application/json": { "schema": { "$ref": reference } },
+                    },
+                }),
+            );
+        }
+
+        json!({
+            "schemas": Value::Object(schemas),
+            "responses": Value::Object(responses),
+        })
+    }
+}
+
+/// Quotes the value as a TypeScript string literal
+fn typescript_string(value: &str) -> String {
+    let mut output = String::with_capacity(value.len() + 2);
+    output.push('"');
+    for char in value.chars() {
+        match char {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            char => output.push(char),
+        }
+    }
+    output.push('"');
+    output
+}
+
+/// Defines a family of unit error types each with a status, code and
+/// message. An optional `register = name;` line generates a function with
+/// that name which registers all the errors with the [ErrorCatalog]
+///
+/// ```
+/// use axum_dyn_error::{define_http_errors, ErrorCatalog, HttpResult};
+///
+/// define_http_errors! {
+///     register = register_user_errors;
+///
+///     /// The requested user doesn't exist
+///     UserNotFound: 404, "USER_NOT_FOUND", "User not found";
+///     UsernameTaken: 409, "USERNAME_TAKEN", "Username is already taken";
+/// }
+///
+/// register_user_errors();
+/// assert_eq!(ErrorCatalog::find("USER_NOT_FOUND").unwrap().status, 404);
+///
+/// pub async fn example_handler() -> HttpResult<()> {
+///     Err(UserNotFound.into())
+/// }
+/// ```
+#[macro_export]
+macro_rules! define_http_errors {
+    (
+        register = $register:ident;
+        $(
+            $(#[$meta:meta])*
+            $name:ident : $status:literal, $code:literal, $message:literal
+        );* $(;)?
+    ) => {
+        $crate::define_http_errors! {
+            $(
+                $(#[$meta])*
+                $name : $status, $code, $message
+            );*
+        }
+
+        /// Registers the errors with the error catalog
+        pub fn $register() {
+            $( $crate::ErrorCatalog::register_type::<$name>(); )*
+        }
+    };
+    (
+        $(
+            $(#[$meta:meta])*
+            $name:ident : $status:literal, $code:literal, $message:literal
+        );* $(;)?
+    ) => {
+        $(
+            $(#[$meta])*
+            #[derive(Debug, Clone, Copy, Default)]
+            pub struct $name;
+
+            const _: u16 = $crate::validate_error_status($status);
+
+            impl ::std::fmt::Display for $name {
+                fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                    f.write_str($message)
+                }
+            }
+
+            impl ::std::error::Error for $name {}
+
+            impl $crate::HttpError for $name {
+                fn status(&self) -> $crate::StatusCode {
+                    <$name as $crate::CatalogError>::ENTRY.status()
+                }
+
+                fn code(&self) -> ::std::option::Option<&str> {
+                    ::std::option::Option::Some($code)
+                }
+            }
+
+            impl $crate::CatalogError for $name {
+                const ENTRY: $crate::CatalogEntry = $crate::CatalogEntry {
+                    name: stringify!($name),
+                    status: $status,
+                    code: $code,
+                    message: $message,
+                };
+            }
+        )*
+    };
+}
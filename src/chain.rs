@@ -0,0 +1,108 @@
+//! Inspection of error source chains for nested [HttpError]s
+
+use std::any::TypeId;
+use std::error::Error;
+use std::sync::RwLock;
+
+use crate::HttpError;
+
+/// Boxed error type used by tower and axum
+pub(crate) type BoxError = Box<dyn Error + Send + Sync>;
+
+/// Function for downcasting an error into a specific [HttpError] type
+type Downcaster = for<'a> fn(&'a (dyn Error + 'static)) -> Option<&'a dyn HttpError>;
+
+/// Function for downcasting a boxed error into a specific [HttpError] type
+type BoxDowncaster = fn(BoxError) -> Result<Box<dyn HttpError>, BoxError>;
+
+/// Functions for downcasting a registered type
+struct Registered {
+    /// The type ID of the registered type
+    type_id: TypeId,
+    /// Function for downcasting references
+    downcast_ref: Downcaster,
+    /// Function for downcasting boxed errors
+    downcast_box: BoxDowncaster,
+}
+
+/// Registered downcasting functions
+static DOWNCASTERS: RwLock<Vec<Registered>> = RwLock::new(Vec::new());
+
+/// Registry of [HttpError] types that can be found within the source chain
+/// of other errors.
+///
+/// Because errors in the source chain are only available as [Error] trait
+/// objects the concrete types must be registered for them to be found
+pub struct ErrorChain;
+
+impl ErrorChain {
+    /// Registers an [HttpError] type so that it can be found within the
+    /// source chain of other errors
+    pub fn register<E: HttpError>() {
+        let mut downcasters = DOWNCASTERS.write().unwrap_or_else(|err| err.into_inner());
+        let type_id = TypeId::of::<E>();
+        if !downcasters
+            .iter()
+            .any(|registered| registered.type_id == type_id)
+        {
+            downcasters.push(Registered {
+                type_id,
+                downcast_ref: downcast::<E>,
+                downcast_box: downcast_box::<E>,
+            });
+        }
+    }
+
+    /// Attempts to downcast the provided error into any of the registered
+    /// [HttpError] types
+    pub fn as_http_error<'a>(error: &'a (dyn Error + 'static)) -> Option<&'a dyn HttpError> {
+        let downcasters = DOWNCASTERS.read().unwrap_or_else(|err| err.into_inner());
+        downcasters
+            .iter()
+            .find_map(|registered| (registered.downcast_ref)(error))
+    }
+
+    /// Attempts to downcast the provided boxed error into any of the
+    /// registered [HttpError] types, returning the original error if
+    /// its not a registered type
+    pub fn downcast_boxed(
+        mut error: Box<dyn Error + Send + Sync>,
+    ) -> Result<Box<dyn HttpError>, Box<dyn Error + Send + Sync>> {
+        let downcasters = DOWNCASTERS.read().unwrap_or_else(|err| err.into_inner());
+        for registered in downcasters.iter() {
+            error = match (registered.downcast_box)(error) {
+                Ok(error) => return Ok(error),
+                Err(error) => error,
+            };
+        }
+        Err(error)
+    }
+
+    /// Finds the deepest registered [HttpError] within the provided error
+    /// and its sources
+    pub fn deepest<'a>(error: Option<&'a (dyn Error + 'static)>) -> Option<&'a dyn HttpError> {
+        let mut deepest = None;
+        let mut current = error;
+        while let Some(error) = current {
+            if let Some(http_error) = Self::as_http_error(error) {
+                deepest = Some(http_error);
+            }
+            current = error.source();
+        }
+        deepest
+    }
+}
+
+/// Downcasts the error into the [HttpError] type `E`
+fn downcast<'a, E: HttpError>(error: &'a (dyn Error + 'static)) -> Option<&'a dyn HttpError> {
+    error
+        .downcast_ref::<E>()
+        .map(|error| error as &dyn HttpError)
+}
+
+/// Downcasts the boxed error into the [HttpError] type `E`
+fn downcast_box<E: HttpError>(error: BoxError) -> Result<Box<dyn HttpError>, BoxError> {
+    error
+        .downcast::<E>()
+        .map(|error| error as Box<dyn HttpError>)
+}
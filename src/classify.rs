@@ -0,0 +1,156 @@
+//! Response classifier for `tower-http` middleware (i.e `TraceLayer`)
+//! that classifies failures using the [ErrorClassification] of error
+//! responses rather than only their status
+
+use std::fmt::Display;
+use std::sync::Arc;
+
+use http::{Response, StatusCode};
+use tower_http::classify::{
+    ClassifiedResponse, ClassifyResponse, NeverClassifyEos, SharedClassifier,
+};
+
+use crate::{ErrorClassification, ErrorKind};
+
+/// Response classifier that understands the [ErrorClassification] added
+/// to responses created from a [DynHttpError](crate::DynHttpError), the
+/// failure class includes the kind of the error so tracing and metrics
+/// middleware can report failures by kind.
+///
+/// By default error responses with a server error (5xx) status are
+/// failures, the same as `tower-http`'s `ServerErrorsAsFailures`. When
+/// failure kinds are set, error responses are failures when their kind
+/// is one of the failure kinds instead. Responses that weren't created
+/// from a [DynHttpError](crate::DynHttpError) are failures when they have
+/// a server error status
+///
+/// ```
+/// use axum_dyn_error::{ErrorKind, ErrorKindAsFailures};
+/// use tower_http::classify::SharedClassifier;
+///
+/// // Only count bugs and upstream failures, not maintenance or rate limits
+/// let classifier = SharedClassifier::new(
+///     ErrorKindAsFailures::new().failure_kinds([ErrorKind::Internal, ErrorKind::Upstream]),
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ErrorKindAsFailures {
+    /// Kinds of errors considered failures, [None] to use the status
+    failure_kinds: Option<Arc<[ErrorKind]>>,
+    /// Whether transient errors are considered successes
+    ignore_transient: bool,
+}
+
+impl ErrorKindAsFailures {
+    /// Creates a new classifier where server errors are failures
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a [MakeClassifier](tower_http::classify::MakeClassifier)
+    /// producing the default classifier
+    pub fn make_classifier() -> SharedClassifier<Self> {
+        SharedClassifier::new(Self::new())
+    }
+
+    /// Sets the kinds of errors that are considered failures
+    pub fn failure_kinds(mut self, kinds: impl IntoIterator<Item = ErrorKind>) -> Self {
+        self.failure_kinds = Some(kinds.into_iter().collect());
+        self
+    }
+
+    /// Sets whether transient errors are considered successes, so that
+    /// expected failures that are retried by clients don't count towards
+    /// error rates (See [HttpError::is_transient](crate::HttpError::is_transient))
+    pub fn ignore_transient(mut self, value: bool) -> Self {
+        self.ignore_transient = value;
+        self
+    }
+
+    /// Checks whether an error response is a failure
+    fn is_failure(&self, status: StatusCode, classification: &ErrorClassification) -> bool {
+        if self.ignore_transient && classification.transient {
+            return false;
+        }
+
+        match &self.failure_kinds {
+            Some(kinds) => kinds.contains(&classification.kind),
+            None => status.is_server_error(),
+        }
+    }
+}
+
+/// Failure class of the [ErrorKindAsFailures] classifier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ErrorFailureClass {
+    /// Response created from a [DynHttpError](crate::DynHttpError)
+    HttpError {
+        /// The status of the response
+        status: StatusCode,
+        /// The kind of the error
+        kind: ErrorKind,
+        /// Whether the error is transient
+        transient: bool,
+    },
+    /// Response with a server error status that wasn't created from a
+    /// [DynHttpError](crate::DynHttpError)
+    StatusCode(StatusCode),
+    /// The service failed with the provided error
+    Error(String),
+}
+
+impl ErrorFailureClass {
+    /// The kind of the failure, errors of the service and responses that
+    /// weren't created from a [DynHttpError](crate::DynHttpError) use the
+    /// kind for their status
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::HttpError { kind, .. } => *kind,
+            Self::StatusCode(status) => ErrorKind::from_status(*status),
+            Self::Error(_) => ErrorKind::Internal,
+        }
+    }
+}
+
+impl Display for ErrorFailureClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::HttpError { status, kind, .. } => write!(f, "Error ({kind}): {status}"),
+            Self::StatusCode(status) => write!(f, "Status code: {status}"),
+            Self::Error(error) => write!(f, "Error: {error}"),
+        }
+    }
+}
+
+impl ClassifyResponse for ErrorKindAsFailures {
+    type FailureClass = ErrorFailureClass;
+    type ClassifyEos = NeverClassifyEos<ErrorFailureClass>;
+
+    fn classify_response<B>(
+        self,
+        res: &Response<B>,
+    ) -> ClassifiedResponse<Self::FailureClass, Self::ClassifyEos> {
+        let status = res.status();
+        let result = match res.extensions().get::<ErrorClassification>() {
+            Some(classification) if self.is_failure(status, classification) => {
+                Err(ErrorFailureClass::HttpError {
+                    status,
+                    kind: classification.kind,
+                    transient: classification.transient,
+                })
+            }
+            Some(_) => Ok(()),
+            None if status.is_server_error() => Err(ErrorFailureClass::StatusCode(status)),
+            None => Ok(()),
+        };
+
+        ClassifiedResponse::Ready(result)
+    }
+
+    fn classify_error<E>(self, error: &E) -> Self::FailureClass
+    where
+        E: Display + 'static,
+    {
+        ErrorFailureClass::Error(error.to_string())
+    }
+}
@@ -0,0 +1,138 @@
+//! Support for the `axum-core` versions used by newer axum major versions
+//!
+//! Only [IntoResponse] is implemented for the newer versions, the layers
+//! and services of the `tower` feature use the `http` 0.2 types so they
+//! can't be used with axum 0.7 or 0.8 routers. Without the
+//! [RequestContextLayer](crate::context::RequestContextLayer) the request
+//! parts aren't known so content negotiation, CORS headers and router
+//! policies don't apply to these responses
+//!
+//! The responses are created using the `axum-core` 0.3 implementation and
+//! then converted into `http` 1.0 responses. Only the [ErrorClassification],
+//! [ErrorIdentity] and [ConnectionDirective] extensions are carried over, extensions added
+//! by [HttpError::extensions](crate::HttpError::extensions) are dropped
+//! as the two versions of [Extensions](http::Extensions) are incompatible
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum_core::response::IntoResponse;
+use bytes::Bytes;
+use http_body::Body as _;
+use http_body_1::Frame;
+use pin_project_lite::pin_project;
+
+use crate::{
+    ConnectionDirective, DynHttpError, ErrorClassification, ErrorIdentity, IntoHttpErrorResponse,
+};
+
+pin_project! {
+    /// Adapter implementing the `http-body` 1.0 trait for the
+    /// `axum-core` 0.3 response body, `data_done` is set once all
+    /// the data frames have been read
+    struct CompatBody {
+        #[pin]
+        inner: axum_core::body::BoxBody,
+        data_done: bool,
+    }
+}
+
+impl http_body_1::Body for CompatBody {
+    type Data = Bytes;
+    type Error = axum_core::Error;
+
+    fn poll_frame(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Frame<Self::Data>, Self::Error>>> {
+        let mut this = self.project();
+
+        if !*this.data_done {
+            match this.inner.as_mut().poll_data(cx) {
+                Poll::Ready(Some(result)) => return Poll::Ready(Some(result.map(Frame::data))),
+                Poll::Ready(None) => *this.data_done = true,
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+
+        match this.inner.poll_trailers(cx) {
+            Poll::Ready(Ok(Some(trailers))) => {
+                Poll::Ready(Some(Ok(Frame::trailers(convert_headers(trailers)))))
+            }
+            Poll::Ready(Ok(None)) => Poll::Ready(None),
+            Poll::Ready(Err(err)) => Poll::Ready(Some(Err(err))),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body_1::SizeHint {
+        let hint = self.inner.size_hint();
+        let mut size_hint = http_body_1::SizeHint::new();
+        size_hint.set_lower(hint.lower());
+        if let Some(upper) = hint.upper() {
+            size_hint.set_upper(upper);
+        }
+        size_hint
+    }
+}
+
+/// Converts a `http` 0.2 header map into a `http` 1.0 header map
+fn convert_headers(headers: http::HeaderMap) -> http_1::HeaderMap {
+    let mut converted = http_1::HeaderMap::with_capacity(headers.len());
+    for (name, value) in headers.iter() {
+        let (Ok(name), Ok(value)) = (
+            http_1::HeaderName::from_bytes(name.as_str().as_bytes()),
+            http_1::HeaderValue::from_bytes(value.as_bytes()),
+        ) else {
+            continue;
+        };
+        converted.append(name, value);
+    }
+    converted
+}
+
+/// Creates the response for the error and converts it into a `http` 1.0 response
+fn into_compat_response<I: IntoHttpErrorResponse>(
+    error: DynHttpError<I>,
+) -> http_1::Response<CompatBody> {
+    let (parts, body) = error.into_response().into_parts();
+
+    let mut response = http_1::Response::new(CompatBody {
+        inner: body,
+        data_done: false,
+    });
+    *response.status_mut() = http_1::StatusCode::from_u16(parts.status.as_u16())
+        .unwrap_or(http_1::StatusCode::INTERNAL_SERVER_ERROR);
+    *response.headers_mut() = convert_headers(parts.headers);
+
+    let extensions = response.extensions_mut();
+    if let Some(classification) = parts.extensions.get::<ErrorClassification>() {
+        extensions.insert(*classification);
+    }
+    if let Some(identity) = parts.extensions.get::<ErrorIdentity>() {
+        extensions.insert(identity.clone());
+    }
+    if let Some(connection) = parts.extensions.get::<ConnectionDirective>() {
+        extensions.insert(*connection);
+    }
+
+    response
+}
+
+#[cfg(feature = "axum-07")]
+impl<I: IntoHttpErrorResponse> axum_core_07::response::IntoResponse for DynHttpError<I> {
+    fn into_response(self) -> axum_core_07::response::Response {
+        into_compat_response(self).map(axum_core_07::body::Body::new)
+    }
+}
+
+#[cfg(feature = "axum-08")]
+impl<I: IntoHttpErrorResponse> axum_core_08::response::IntoResponse for DynHttpError<I> {
+    fn into_response(self) -> axum_core_08::response::Response {
+        into_compat_response(self).map(axum_core_08::body::Body::new)
+    }
+}
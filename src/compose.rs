@@ -0,0 +1,301 @@
+//! Wrapper formatters that decorate an inner [IntoHttpErrorResponse]
+//! allowing formatters to be composed into a pipeline:
+//!
+//! ```
+//! use axum_dyn_error::{HttpResult, Redacted, TextErrorResponse, WithHeaders, ErrorHeaders, HttpError};
+//! use axum_dyn_error::StatusCode;
+//! use http::{HeaderMap, HeaderValue};
+//!
+//! pub struct ServiceHeader;
+//!
+//! impl ErrorHeaders for ServiceHeader {
+//!     fn headers(_error: &dyn HttpError, headers: &mut HeaderMap) {
+//!         headers.insert("x-service", HeaderValue::from_static("users"));
+//!     }
+//! }
+//!
+//! pub type MyHttpResult<T> = HttpResult<T, WithHeaders<Redacted<TextErrorResponse>, ServiceHeader>>;
+//! ```
+
+use std::marker::PhantomData;
+
+use axum_core::response::Response;
+use http::{request::Parts, HeaderMap};
+
+use crate::{rewrite::RewrittenError, HttpError, IntoHttpErrorResponse};
+
+/// Provides additional headers for the [WithHeaders] formatter
+pub trait ErrorHeaders {
+    /// Adds the headers for the provided error
+    fn headers(error: &dyn HttpError, headers: &mut HeaderMap);
+}
+
+/// Formatter that adds the headers from `H` to the responses created
+/// by the inner formatter `I`
+pub struct WithHeaders<I, H> {
+    /// Marker for storing the formatter and headers types
+    _marker: PhantomData<fn() -> (I, H)>,
+}
+
+impl<I, H> IntoHttpErrorResponse for WithHeaders<I, H>
+where
+    I: IntoHttpErrorResponse,
+    H: ErrorHeaders,
+{
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        Self::into_response_with_parts(error, None)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        let mut headers = HeaderMap::new();
+        H::headers(error.as_ref(), &mut headers);
+
+        let mut response = I::into_response_with_parts(error, parts);
+        response.headers_mut().extend(headers);
+        response
+    }
+}
+
+/// Replaces the reason of errors before they reach the formatter used
+/// by the [Redacted] formatter
+pub trait Redactor {
+    /// Redacts the provided reason, returns [None] if the reason
+    /// should be left unchanged
+    fn redact(error: &dyn HttpError, reason: &str) -> Option<String>;
+}
+
+/// [Redactor] that replaces the reason of server errors (5xx) with the
+/// canonical reason for the status code
+pub struct ServerErrorRedactor;
+
+impl Redactor for ServerErrorRedactor {
+    fn redact(error: &dyn HttpError, _reason: &str) -> Option<String> {
+        let status = error.status();
+        if !status.is_server_error() {
+            return None;
+        }
+
+        Some(
+            status
+                .canonical_reason()
+                .unwrap_or("Server error")
+                .to_string(),
+        )
+    }
+}
+
+/// Formatter that redacts the reason of errors using the redactor `R`
+/// before creating the response using the inner formatter `I`
+pub struct Redacted<I, R = ServerErrorRedactor> {
+    /// Marker for storing the formatter and redactor types
+    _marker: PhantomData<fn() -> (I, R)>,
+}
+
+impl<I, R> IntoHttpErrorResponse for Redacted<I, R>
+where
+    I: IntoHttpErrorResponse,
+    R: Redactor,
+{
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        Self::into_response_with_parts(error, None)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        match R::redact(error.as_ref(), &error.reason()) {
+            Some(reason) => {
+                let mut error = RewrittenError::new(error);
+                error.reason = Some(reason);
+                error.redacted = true;
+                I::into_response_with_parts(Box::new(error), parts)
+            }
+            None => I::into_response_with_parts(error, parts),
+        }
+    }
+}
+
+/// Formatter that copies the `x-request-id` header from the request onto
+/// the responses created by the inner formatter `I`, requires the
+/// request parts to be captured by the [RequestContextLayer](crate::context::RequestContextLayer)
+#[cfg(feature = "tower")]
+pub struct WithRequestId<I> {
+    /// Marker for storing the formatter type
+    _marker: PhantomData<fn() -> I>,
+}
+
+#[cfg(feature = "tower")]
+impl<I> IntoHttpErrorResponse for WithRequestId<I>
+where
+    I: IntoHttpErrorResponse,
+{
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        I::into_response(error)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        use crate::context::REQUEST_ID_HEADER;
+
+        let request_id = parts.and_then(|parts| parts.headers.get(REQUEST_ID_HEADER).cloned());
+
+        let mut response = I::into_response_with_parts(error, parts);
+        if let Some(request_id) = request_id {
+            response.headers_mut().insert(REQUEST_ID_HEADER, request_id);
+        }
+        response
+    }
+}
+
+/// CORS rules used by the [WithCors] formatter, these should match the
+/// rules of the CORS layer used by the application
+///
+/// Allowing credentials for the `*` origin is rejected at compile time:
+///
+/// ```compile_fail
+/// use axum_dyn_error::{
+///     CorsRules, IntoHttpErrorResponse, MessageHttpError, StatusCode, TextErrorResponse, WithCors,
+/// };
+///
+/// pub struct AnyCors;
+///
+/// impl CorsRules for AnyCors {
+///     const ALLOW_CREDENTIALS: bool = true;
+/// }
+///
+/// let error = MessageHttpError::new(StatusCode::NOT_FOUND, "Not found");
+/// WithCors::<TextErrorResponse, AnyCors>::into_response(Box::new(error));
+/// ```
+#[cfg(feature = "tower")]
+pub trait CorsRules {
+    /// Origins allowed to read error responses, `*` allows any origin
+    const ALLOWED_ORIGINS: &'static [&'static str] = &["*"];
+
+    /// Whether credentialed requests are allowed, the request origin is
+    /// echoed if its one of the allowed origins. Credentials can't be
+    /// combined with the `*` origin, doing so fails to compile when the
+    /// rules are used by [WithCors]
+    const ALLOW_CREDENTIALS: bool = false;
+
+    /// Response headers the browser is allowed to expose to the page
+    const EXPOSE_HEADERS: &'static [&'static str] = &[];
+}
+
+/// Formatter that adds CORS headers to the responses created by the
+/// inner formatter `I` using the rules from `C`, so that browsers can read
+/// the error body of errors that occur before the CORS layer runs (i.e in
+/// an authentication layer outside of the CORS layer) instead of reporting
+/// an opaque network error
+///
+/// The `Origin` of the request is taken from the request parts captured
+/// by the [RequestContextLayer](crate::context::RequestContextLayer), headers
+/// already added by the inner formatter are kept
+///
+/// ```
+/// use axum_dyn_error::{CorsRules, HttpResult, JsonErrorResponse, WithCors};
+///
+/// pub struct AppCors;
+///
+/// impl CorsRules for AppCors {
+///     const ALLOWED_ORIGINS: &'static [&'static str] = &["https://app.example.com"];
+///     const ALLOW_CREDENTIALS: bool = true;
+/// }
+///
+/// pub type MyHttpResult<T> = HttpResult<T, WithCors<JsonErrorResponse, AppCors>>;
+/// ```
+#[cfg(feature = "tower")]
+pub struct WithCors<I, C> {
+    /// Marker for storing the formatter and rules types
+    _marker: PhantomData<fn() -> (I, C)>,
+}
+
+/// Compile time validation of [CorsRules]
+#[cfg(feature = "tower")]
+struct ValidCorsRules<C>(PhantomData<fn() -> C>);
+
+#[cfg(feature = "tower")]
+impl<C: CorsRules> ValidCorsRules<C> {
+    /// Allowing credentials for any origin would let every site make
+    /// credentialed requests and read the responses
+    const ASSERT: () = assert!(
+        !(C::ALLOW_CREDENTIALS && contains_any_origin(C::ALLOWED_ORIGINS)),
+        "CorsRules cannot allow credentials for the `*` origin"
+    );
+}
+
+/// Checks whether the origins include the `*` origin
+#[cfg(feature = "tower")]
+const fn contains_any_origin(origins: &[&str]) -> bool {
+    let mut index = 0;
+    while index < origins.len() {
+        let origin = origins[index].as_bytes();
+        if origin.len() == 1 && origin[0] == b'*' {
+            return true;
+        }
+        index += 1;
+    }
+    false
+}
+
+#[cfg(feature = "tower")]
+impl<I, C> IntoHttpErrorResponse for WithCors<I, C>
+where
+    I: IntoHttpErrorResponse,
+    C: CorsRules,
+{
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        Self::into_response_with_parts(error, None)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        use http::header::{
+            ACCESS_CONTROL_ALLOW_CREDENTIALS, ACCESS_CONTROL_ALLOW_ORIGIN,
+            ACCESS_CONTROL_EXPOSE_HEADERS, ORIGIN, VARY,
+        };
+        use http::HeaderValue;
+
+        #[allow(clippy::let_unit_value)]
+        let () = ValidCorsRules::<C>::ASSERT;
+
+        let origin = parts.and_then(|parts| parts.headers.get(ORIGIN).cloned());
+        let any_origin = C::ALLOWED_ORIGINS.contains(&"*");
+
+        let allow_origin = match origin {
+            Some(_) if any_origin => Some(HeaderValue::from_static("*")),
+            Some(origin) => origin
+                .to_str()
+                .is_ok_and(|value| C::ALLOWED_ORIGINS.contains(&value))
+                .then_some(origin),
+            // The origin isn't known without the request parts
+            None if any_origin => Some(HeaderValue::from_static("*")),
+            None => None,
+        };
+
+        let mut response = I::into_response_with_parts(error, parts);
+        let Some(allow_origin) = allow_origin else {
+            return response;
+        };
+
+        let headers = response.headers_mut();
+        if headers.contains_key(ACCESS_CONTROL_ALLOW_ORIGIN) {
+            return response;
+        }
+
+        if allow_origin != "*" {
+            headers.append(VARY, HeaderValue::from_static("origin"));
+        }
+        headers.insert(ACCESS_CONTROL_ALLOW_ORIGIN, allow_origin);
+
+        if C::ALLOW_CREDENTIALS {
+            headers.insert(
+                ACCESS_CONTROL_ALLOW_CREDENTIALS,
+                HeaderValue::from_static("true"),
+            );
+        }
+
+        if !C::EXPOSE_HEADERS.is_empty() {
+            if let Ok(value) = HeaderValue::from_str(&C::EXPOSE_HEADERS.join(", ")) {
+                headers.insert(ACCESS_CONTROL_EXPOSE_HEADERS, value);
+            }
+        }
+
+        response
+    }
+}
@@ -0,0 +1,370 @@
+//! Global configuration for how errors are converted into responses
+
+use std::sync::{Arc, RwLock};
+#[cfg(feature = "log")]
+use std::time::Duration;
+
+use http::{HeaderValue, StatusCode};
+
+/// Currently installed global configuration
+static CONFIG: RwLock<Option<Arc<ErrorConfig>>> = RwLock::new(None);
+
+/// Format used by the [Display](std::fmt::Display) implementation of
+/// [DynHttpError](crate::DynHttpError)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DisplayFormat {
+    /// The status line followed by the message of the error
+    /// (i.e `404 Not Found: User not found`)
+    #[default]
+    StatusLine,
+    /// Only the message of the error (i.e `User not found`)
+    Message,
+}
+
+/// Configuration applied when converting [HttpError]s into responses,
+/// install it once at startup using [ErrorConfig::install]
+#[derive(Debug, Clone)]
+pub struct ErrorConfig {
+    /// Optional policy for hiding the existence of resources
+    pub(crate) enumeration: Option<EnumerationPolicy>,
+    /// Maximum length in bytes of reasons included in responses
+    pub(crate) max_reason_length: Option<usize>,
+    /// Marker appended to reasons that have been truncated
+    truncation_marker: String,
+    /// Default `Cache-Control` header for error responses
+    pub(crate) cache_control: Option<HeaderValue>,
+    /// Whether to resolve details from nested errors in the source chain
+    pub(crate) resolve_from_chain: bool,
+    /// Window in which errors with the same fingerprint are only logged once
+    #[cfg(feature = "log")]
+    pub(crate) dedup_window: Option<Duration>,
+    /// Format used when displaying errors
+    pub(crate) display_format: DisplayFormat,
+    /// Whether debug output only includes the redacted reason
+    pub(crate) redact_debug: bool,
+    /// Status used for errors converted without a more specific status
+    pub(crate) default_status: StatusCode,
+}
+
+impl Default for ErrorConfig {
+    fn default() -> Self {
+        Self {
+            enumeration: None,
+            max_reason_length: None,
+            truncation_marker: "...".to_string(),
+            cache_control: Some(HeaderValue::from_static("no-store")),
+            resolve_from_chain: false,
+            #[cfg(feature = "log")]
+            dedup_window: None,
+            display_format: DisplayFormat::StatusLine,
+            redact_debug: false,
+            default_status: StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+impl ErrorConfig {
+    /// Creates a new configuration using the defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum length in bytes of the reason included in error
+    /// responses, longer reasons are truncated and have the truncation
+    /// marker appended. The same limit applies to the string values within
+    /// the error details (`json` feature). Logs still contain the full reason
+    pub fn max_reason_length(mut self, length: usize) -> Self {
+        self.max_reason_length = Some(length);
+        self
+    }
+
+    /// Sets the marker appended to truncated reasons (Default: "...")
+    pub fn truncation_marker(mut self, marker: impl Into<String>) -> Self {
+        self.truncation_marker = marker.into();
+        self
+    }
+
+    /// Truncates the provided text to the maximum reason length, returns
+    /// [None] if the text doesn't need to be truncated
+    pub fn truncate(&self, text: &str) -> Option<String> {
+        let max = self.max_reason_length?;
+        if text.len() <= max {
+            return None;
+        }
+
+        // Find the closest character boundary to truncate at
+        let mut end = max;
+        while !text.is_char_boundary(end) {
+            end -= 1;
+        }
+
+        let mut truncated = String::with_capacity(end + self.truncation_marker.len());
+        truncated.push_str(&text[..end]);
+        truncated.push_str(&self.truncation_marker);
+        Some(truncated)
+    }
+
+    /// Truncates the string values within the details to the maximum
+    /// reason length, returns whether any values were truncated
+    #[cfg(all(feature = "json", feature = "axum"))]
+    pub(crate) fn truncate_details(&self, details: &mut serde_json::Value) -> bool {
+        use serde_json::Value;
+
+        match details {
+            Value::String(text) => match self.truncate(text) {
+                Some(truncated) => {
+                    *text = truncated;
+                    true
+                }
+                None => false,
+            },
+            Value::Array(values) => values.iter_mut().fold(false, |truncated, value| {
+                self.truncate_details(value) | truncated
+            }),
+            Value::Object(values) => values.values_mut().fold(false, |truncated, value| {
+                self.truncate_details(value) | truncated
+            }),
+            _ => false,
+        }
+    }
+
+    /// Sets the default `Cache-Control` header included in error responses
+    /// that don't provide their own (Default: "no-store"), [None] will
+    /// prevent the header from being added
+    pub fn cache_control(mut self, value: Option<HeaderValue>) -> Self {
+        self.cache_control = value;
+        self
+    }
+
+    /// Sets whether the status, code and headers of errors should be resolved
+    /// from the deepest [HttpError](crate::HttpError) within the source chain
+    /// of the error rather than the outermost wrapper. Only types registered
+    /// with [ErrorChain](crate::ErrorChain) can be found in the source chain
+    pub fn resolve_from_chain(mut self, value: bool) -> Self {
+        self.resolve_from_chain = value;
+        self
+    }
+
+    /// Sets the window in which errors with the same fingerprint (type,
+    /// status and code) are only logged once, the number of duplicates
+    /// suppressed is logged the next time the error is logged or once the
+    /// window has passed and another error is logged.
+    ///
+    /// Only logs are deduplicated, the [ErrorSink](crate::sink::ErrorSink)s,
+    /// audit sinks and metrics still receive every error so that counts and
+    /// alerting stay accurate during a storm. Sinks that forward to rate
+    /// limited services should sample or coalesce events themselves
+    #[cfg(feature = "log")]
+    pub fn dedup_window(mut self, window: Duration) -> Self {
+        self.dedup_window = Some(window);
+        self
+    }
+
+    /// Sets the format used when displaying a [DynHttpError](crate::DynHttpError)
+    /// (Default: [DisplayFormat::StatusLine]), use [DisplayFormat::Message]
+    /// to keep the format expected by existing log parsers
+    pub fn display_format(mut self, format: DisplayFormat) -> Self {
+        self.display_format = format;
+        self
+    }
+
+    /// Sets whether the [Debug](std::fmt::Debug) output of a
+    /// [DynHttpError](crate::DynHttpError) only includes the type name,
+    /// status and the reason as it would be exposed in the response (after
+    /// the exposure rules and redactors of the current
+    /// [ErrorPolicy](crate::ErrorPolicy) and the reason truncation), so that
+    /// `{:?}` in application logs doesn't leak the inner error
+    pub fn redact_debug(mut self, value: bool) -> Self {
+        self.redact_debug = value;
+        self
+    }
+
+    /// Sets the status used by the blanket conversions of errors that
+    /// don't have a more specific status from the [ErrorMapper](crate::ErrorMapper)
+    /// (i.e boxed errors and anyhow errors) (Default: 500 Internal Server
+    /// Error). Can be overridden per router using [ErrorPolicy::default_status](crate::ErrorPolicy::default_status)
+    pub fn default_status(mut self, status: StatusCode) -> Self {
+        self.default_status = status;
+        self
+    }
+
+    /// Sets the policy used to prevent resource enumeration
+    pub fn enumeration_policy(mut self, policy: EnumerationPolicy) -> Self {
+        self.enumeration = Some(policy);
+        self
+    }
+
+    /// Installs this configuration as the global configuration
+    /// replacing any previously installed configuration
+    pub fn install(self) {
+        let mut config = CONFIG.write().unwrap_or_else(|err| err.into_inner());
+        *config = Some(Arc::new(self));
+    }
+
+    /// Obtains the currently installed configuration, or the
+    /// default configuration if none has been installed
+    pub fn current() -> Arc<ErrorConfig> {
+        let config = CONFIG.read().unwrap_or_else(|err| err.into_inner());
+        config.clone().unwrap_or_default()
+    }
+}
+
+/// Determines the status used by blanket conversions for errors without
+/// a more specific status, from the policy of the current router followed
+/// by the configuration
+pub(crate) fn default_status() -> StatusCode {
+    #[cfg(feature = "tower")]
+    if let Some(policy) = crate::ErrorPolicy::current() {
+        if let Some(status) = policy.default_status_override() {
+            return status;
+        }
+
+        if let Some(config) = policy.config_override() {
+            return config.default_status;
+        }
+    }
+
+    ErrorConfig::current().default_status
+}
+
+/// Obtains the configuration for the current request, the configuration
+/// of the current router policy or the global configuration
+#[cfg(feature = "axum")]
+pub(crate) fn current_config() -> Arc<ErrorConfig> {
+    #[cfg(feature = "tower")]
+    if let Some(config) = crate::ErrorPolicy::current().and_then(|policy| policy.config_override())
+    {
+        return config;
+    }
+
+    ErrorConfig::current()
+}
+
+/// Policy that rewrites error responses so that clients cannot
+/// determine whether a resource exists based on the error they
+/// receive (i.e 403 for existing resources and 404 for missing ones)
+///
+/// Rewritten errors only keep the replacement status and reason, the
+/// code, details, help, remediation and headers of the original error
+/// are hidden so the response is identical to a real 404. `Set-Cookie`
+/// and `Clear-Site-Data` headers are kept so that expired sessions are
+/// still cleared, and 401 responses keep their `WWW-Authenticate` challenges.
+/// Responses provided by the error itself (i.e `DynHttpError::from_response`)
+/// are replaced by a formatted response
+///
+/// Matching against route prefixes requires the request path to be
+/// known, which requires the [RequestContextLayer](crate::context::RequestContextLayer)
+/// (`tower` feature). A policy without any prefixes applies to every route
+///
+/// ```
+/// # #[cfg(all(feature = "json", feature = "render"))]
+/// # fn main() {
+/// use axum_dyn_error::{
+///     render_error, DynHttpError, EnumerationPolicy, ErrorConfig, HttpErrorBuilder,
+///     JsonErrorResponse, StatusCode,
+/// };
+/// use http::{header::LOCATION, HeaderValue};
+///
+/// ErrorConfig::new()
+///     .enumeration_policy(EnumerationPolicy::new())
+///     .install();
+///
+/// let forbidden: DynHttpError<JsonErrorResponse> = HttpErrorBuilder::new(StatusCode::FORBIDDEN)
+///     .reason("You don't own this document")
+///     .code("NOT_OWNER")
+///     .help("Ask the owner to share the document")
+///     .remediation("Request access")
+///     .header(LOCATION, HeaderValue::from_static("/documents/42/access"))
+///     .detail("owner", "alice")
+///     .build();
+/// let not_found: DynHttpError<JsonErrorResponse> = DynHttpError::not_found("Not Found");
+///
+/// let masked = render_error(forbidden, None);
+/// let real = render_error(not_found, None);
+/// assert_eq!(masked.status(), real.status());
+/// assert_eq!(masked.headers(), real.headers());
+/// assert_eq!(masked.body(), real.body());
+/// # }
+/// # #[cfg(not(all(feature = "json", feature = "render")))]
+/// # fn main() {}
+/// ```
+#[derive(Debug, Clone)]
+pub struct EnumerationPolicy {
+    /// Route prefixes the policy applies to
+    prefixes: Vec<String>,
+    /// Whether 403 errors should be responded to as 404
+    forbidden_as_not_found: bool,
+    /// Whether the reason for 401 errors should be stripped
+    strip_unauthorized: bool,
+}
+
+impl Default for EnumerationPolicy {
+    fn default() -> Self {
+        Self {
+            prefixes: Vec::new(),
+            forbidden_as_not_found: true,
+            strip_unauthorized: true,
+        }
+    }
+}
+
+impl EnumerationPolicy {
+    /// Creates a new policy that rewrites 403 errors as 404 and strips
+    /// the reason from 401 errors
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a route prefix that this policy should apply to
+    pub fn prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefixes.push(prefix.into());
+        self
+    }
+
+    /// Sets whether 403 errors should be responded to as 404
+    pub fn forbidden_as_not_found(mut self, value: bool) -> Self {
+        self.forbidden_as_not_found = value;
+        self
+    }
+
+    /// Sets whether the reason for 401 errors should be replaced with
+    /// a generic message
+    pub fn strip_unauthorized(mut self, value: bool) -> Self {
+        self.strip_unauthorized = value;
+        self
+    }
+
+    /// Checks whether the policy applies to the provided request path,
+    /// when the path is unknown only policies without prefixes apply.
+    ///
+    /// Prefixes match on segment boundaries, `/api` matches `/api` and
+    /// `/api/users` but not `/apix`
+    pub fn matches(&self, path: Option<&str>) -> bool {
+        if self.prefixes.is_empty() {
+            return true;
+        }
+
+        match path {
+            Some(path) => self.prefixes.iter().any(|prefix| {
+                path.strip_prefix(prefix.as_str()).is_some_and(|rest| {
+                    rest.is_empty() || rest.starts_with('/') || prefix.ends_with('/')
+                })
+            }),
+            None => false,
+        }
+    }
+
+    /// Determines the replacement status and reason for an error with
+    /// the provided status, returns [None] if the error should be left unchanged
+    #[cfg(feature = "axum")]
+    pub(crate) fn rewrite(&self, status: StatusCode) -> Option<(StatusCode, String)> {
+        let status = match status {
+            StatusCode::FORBIDDEN if self.forbidden_as_not_found => StatusCode::NOT_FOUND,
+            StatusCode::UNAUTHORIZED if self.strip_unauthorized => StatusCode::UNAUTHORIZED,
+            _ => return None,
+        };
+
+        let reason = status.canonical_reason().unwrap_or_default().to_string();
+        Some((status, reason))
+    }
+}
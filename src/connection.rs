@@ -0,0 +1,16 @@
+//! Connection level signaling for error responses
+
+/// Directive for the connection an error response is sent on, used to
+/// drain unhealthy instances behind load balancers
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ConnectionDirective {
+    /// Leave the connection as is
+    #[default]
+    Keep,
+    /// Close the connection after the response is sent using the
+    /// `Connection: close` header (HTTP/1.1). The directive is also added
+    /// to the response extensions so that servers supporting HTTP/2 can
+    /// send a GOAWAY frame
+    Close,
+}
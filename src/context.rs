@@ -0,0 +1,129 @@
+//! Layer for capturing details about the current request so that they
+//! are available while converting errors into responses
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use http::{header::HeaderName, request::Parts, HeaderValue, Request, Uri};
+use tokio::task::futures::TaskLocalFuture;
+use tower_layer::Layer;
+use tower_service::Service;
+
+tokio::task_local! {
+    /// Context for the request currently being handled
+    static REQUEST_CONTEXT: RequestContext;
+}
+
+/// Header containing the ID of the request
+pub const REQUEST_ID_HEADER: HeaderName = HeaderName::from_static("x-request-id");
+
+/// Details about the request that produced an error
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// Copy of the request parts (Excluding extensions)
+    parts: Arc<Parts>,
+    /// When the request started
+    started: Instant,
+}
+
+impl RequestContext {
+    /// Creates a request context from the provided request, the method,
+    /// URI, version and headers of the request are copied
+    pub fn from_request<B>(request: &Request<B>) -> Self {
+        let mut copy = Request::new(());
+        *copy.method_mut() = request.method().clone();
+        *copy.uri_mut() = request.uri().clone();
+        *copy.version_mut() = request.version();
+        *copy.headers_mut() = request.headers().clone();
+
+        let (parts, _) = copy.into_parts();
+        Self {
+            parts: Arc::new(parts),
+            started: Instant::now(),
+        }
+    }
+
+    /// The copy of the request parts, these parts don't contain the
+    /// extensions from the original request
+    pub fn parts(&self) -> &Parts {
+        &self.parts
+    }
+
+    /// The ID of the request from the `x-request-id` header
+    pub fn request_id(&self) -> Option<&HeaderValue> {
+        self.parts.headers.get(REQUEST_ID_HEADER)
+    }
+
+    /// The URI of the request
+    pub fn uri(&self) -> &Uri {
+        &self.parts.uri
+    }
+
+    /// The path portion of the request URI
+    pub fn path(&self) -> &str {
+        self.parts.uri.path()
+    }
+
+    /// When the request started (When the context was created)
+    pub fn started(&self) -> Instant {
+        self.started
+    }
+
+    /// Time elapsed since the request started, useful for attributing
+    /// latency to failed requests
+    pub fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    /// Obtains a copy of the context for the current request, this will
+    /// be [None] when called outside of a [RequestContextLayer]
+    pub fn current() -> Option<RequestContext> {
+        REQUEST_CONTEXT.try_with(Clone::clone).ok()
+    }
+}
+
+/// Layer that captures the [RequestContext] for each request so that
+/// it can be used when converting errors into responses
+#[derive(Debug, Clone, Copy, Default)]
+pub struct RequestContextLayer;
+
+impl RequestContextLayer {
+    /// Creates a new request context layer
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl<S> Layer<S> for RequestContextLayer {
+    type Service = RequestContextService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RequestContextService { inner }
+    }
+}
+
+/// Service created by [RequestContextLayer]
+#[derive(Debug, Clone)]
+pub struct RequestContextService<S> {
+    /// The inner service
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for RequestContextService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<RequestContext, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let context = RequestContext::from_request(&request);
+        REQUEST_CONTEXT.scope(context, self.inner.call(request))
+    }
+}
@@ -0,0 +1,47 @@
+//! Conversion of errors into [HttpError]s for generic code
+
+use std::error::Error;
+
+use crate::{ErrorChain, GenericServerError, HttpError};
+
+/// Conversion into a boxed [HttpError], implemented for [HttpError] types,
+/// boxed errors and anyhow errors (`anyhow` feature) so that generic service
+/// layers can bound on `E: IntoHttpError` to produce status aware errors
+///
+/// ```
+/// use axum_dyn_error::{DynHttpError, IntoHttpError};
+///
+/// fn handle<E: IntoHttpError>(error: E) -> DynHttpError {
+///     DynHttpError::from_error(error)
+/// }
+/// ```
+pub trait IntoHttpError {
+    /// Converts the error into a boxed [HttpError]
+    fn into_http_error(self) -> Box<dyn HttpError>;
+}
+
+impl<E: HttpError> IntoHttpError for E {
+    fn into_http_error(self) -> Box<dyn HttpError> {
+        Box::new(self)
+    }
+}
+
+/// Boxed errors of types registered with the [ErrorChain] keep their type,
+/// other errors become a [GenericServerError]
+impl IntoHttpError for Box<dyn Error + Send + Sync> {
+    fn into_http_error(self) -> Box<dyn HttpError> {
+        match ErrorChain::downcast_boxed(self) {
+            Ok(error) => error,
+            Err(error) => Box::new(GenericServerError::from_boxed(error)),
+        }
+    }
+}
+
+/// Anyhow errors are converted the same way as the [DynHttpError](crate::DynHttpError)
+/// conversion, consulting the [ErrorMapper](crate::ErrorMapper)
+#[cfg(feature = "anyhow")]
+impl IntoHttpError for anyhow::Error {
+    fn into_http_error(self) -> Box<dyn HttpError> {
+        Box::new(crate::AnyhowHttpError::from_anyhow(self))
+    }
+}
@@ -0,0 +1,121 @@
+//! Support for the errors of CSV imports (`csv`)
+//!
+//! Malformed CSV data is a 400 Bad Request error and records that fail to
+//! deserialize are 422 Unprocessable Entity errors, the position of the
+//! error is included in the details. Errors can also be converted into
+//! [ErrorEntry]s to collect every failed record of an import into a
+//! [MultiHttpError](crate::MultiHttpError)
+//!
+//! ```
+//! use axum_dyn_error::{ErrorEntry, HttpResult, MultiHttpError, StatusCode};
+//!
+//! pub async fn import(body: String) -> HttpResult<String> {
+//!     let mut reader = csv::Reader::from_reader(body.as_bytes());
+//!     let mut rows = Vec::new();
+//!     let mut errors = MultiHttpError::new()
+//!         .status(StatusCode::UNPROCESSABLE_ENTITY)
+//!         .reason("Invalid rows");
+//!
+//!     for row in reader.deserialize::<(String, u32)>() {
+//!         match row {
+//!             Ok(row) => rows.push(row),
+//!             Err(error) => errors.push(ErrorEntry::from(&error)),
+//!         }
+//!     }
+//!
+//!     if !errors.is_empty() {
+//!         return Err(errors.into());
+//!     }
+//!
+//!     Ok(format!("Imported {} rows", rows.len()))
+//! }
+//! ```
+
+use csv::{Error, ErrorKind};
+use http::StatusCode;
+
+use crate::{ErrorEntry, HttpError};
+
+/// Column of the error, columns start at 1 to match spreadsheets
+fn error_column(error: &Error) -> Option<u64> {
+    match error.kind() {
+        ErrorKind::Deserialize { err, .. } => err.field().map(|field| field + 1),
+        _ => None,
+    }
+}
+
+/// Machine readable code for the error
+fn error_code(error: &Error) -> &'static str {
+    match error.kind() {
+        ErrorKind::Utf8 { .. } => "csv_invalid_utf8",
+        ErrorKind::UnequalLengths { .. } => "csv_unequal_lengths",
+        ErrorKind::Deserialize { .. } => "csv_invalid_value",
+        _ => "csv_error",
+    }
+}
+
+impl HttpError for Error {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_client_error() {
+            log::debug!("{self}");
+        } else {
+            log::error!("{self}{}", crate::location::log_suffix());
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self.kind() {
+            ErrorKind::Utf8 { .. } | ErrorKind::UnequalLengths { .. } => StatusCode::BAD_REQUEST,
+            ErrorKind::Deserialize { .. } => StatusCode::UNPROCESSABLE_ENTITY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn reason(&self) -> String {
+        if self.status().is_client_error() {
+            self.to_string()
+        } else {
+            "Server error".to_string()
+        }
+    }
+
+    fn code(&self) -> Option<&str> {
+        Some(error_code(self))
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        let position = self.position()?;
+        Some(serde_json::json!({
+            "line": position.line(),
+            "record": position.record(),
+            "column": error_column(self),
+        }))
+    }
+}
+
+/// Creates an entry with the line, column and code of the error
+impl From<&Error> for ErrorEntry {
+    fn from(value: &Error) -> Self {
+        let message = match value.kind() {
+            ErrorKind::Deserialize { err, .. } => err.kind().to_string(),
+            _ => value.to_string(),
+        };
+
+        let mut entry = ErrorEntry::new(message).code(error_code(value));
+        if let Some(position) = value.position() {
+            entry = entry.line(position.line());
+        }
+        if let Some(column) = error_column(value) {
+            entry = entry.column(column);
+        }
+        entry
+    }
+}
+
+impl From<Error> for ErrorEntry {
+    fn from(value: Error) -> Self {
+        Self::from(&value)
+    }
+}
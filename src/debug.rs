@@ -0,0 +1,199 @@
+//! Developer mode that exposes debugging information about server
+//! errors in responses, this should never be enabled in production
+
+use std::backtrace::{Backtrace, BacktraceStatus};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use http::{
+    header::{AUTHORIZATION, COOKIE, PROXY_AUTHORIZATION},
+    request::Parts,
+};
+
+use crate::{html::escape_html, HttpError};
+
+/// Whether debug errors are currently enabled
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Runtime toggle for including debugging information (error type, error
+/// chain and backtrace) in server error responses, disabled by default
+pub struct DebugErrors;
+
+impl DebugErrors {
+    /// Enables including debugging information in responses
+    pub fn enable() {
+        Self::set(true)
+    }
+
+    /// Disables including debugging information in responses
+    pub fn disable() {
+        Self::set(false)
+    }
+
+    /// Sets whether debugging information is included in responses
+    pub fn set(enabled: bool) {
+        ENABLED.store(enabled, Ordering::Relaxed)
+    }
+
+    /// Checks whether debugging information is included in responses
+    pub fn is_enabled() -> bool {
+        ENABLED.load(Ordering::Relaxed)
+    }
+}
+
+/// Debugging information about an error
+#[derive(Debug, Clone)]
+pub struct DebugInfo {
+    /// The full type name of the error
+    pub type_name: String,
+    /// The display messages of the error and each of its sources
+    pub chain: Vec<String>,
+    /// Advice on how to resolve the error (See [HttpError::help])
+    pub help: Option<String>,
+    /// Steps for remediating the error (See [HttpError::remediation])
+    pub remediation: Option<String>,
+    /// The captured backtrace if one is available
+    pub backtrace: Option<String>,
+    /// Information about the request that caused the error
+    pub request: Option<RequestInfo>,
+}
+
+/// Debugging information about the request that caused an error
+#[derive(Debug, Clone)]
+pub struct RequestInfo {
+    /// The request method
+    pub method: String,
+    /// The request URI
+    pub uri: String,
+    /// The request headers, sensitive header values are masked
+    pub headers: Vec<(String, String)>,
+}
+
+impl RequestInfo {
+    /// Collects the request information from the provided parts
+    pub fn from_parts(parts: &Parts) -> Self {
+        let headers = parts
+            .headers
+            .iter()
+            .map(|(name, value)| {
+                let value =
+                    if name == AUTHORIZATION || name == PROXY_AUTHORIZATION || name == COOKIE {
+                        "[masked]".to_string()
+                    } else {
+                        String::from_utf8_lossy(value.as_bytes()).into_owned()
+                    };
+                (name.to_string(), value)
+            })
+            .collect();
+
+        Self {
+            method: parts.method.to_string(),
+            uri: parts.uri.to_string(),
+            headers,
+        }
+    }
+}
+
+impl DebugInfo {
+    /// Collects the debugging information for the provided error
+    pub fn from_error(error: &dyn HttpError) -> Self {
+        let mut chain = vec![error.to_string()];
+        let mut current = error.source();
+        while let Some(source) = current {
+            chain.push(source.to_string());
+            current = source.source();
+        }
+
+        Self {
+            type_name: error.type_name().to_string(),
+            chain,
+            help: error.help(),
+            remediation: error.remediation(),
+            backtrace: find_backtrace(error)
+                .filter(|backtrace| backtrace.status() == BacktraceStatus::Captured)
+                .map(Backtrace::to_string),
+            request: None,
+        }
+    }
+
+    /// Collects the debugging information for the provided error including
+    /// information about the request from the provided parts
+    pub fn from_error_with_parts(error: &dyn HttpError, parts: Option<&Parts>) -> Self {
+        let mut info = Self::from_error(error);
+        info.request = parts.map(RequestInfo::from_parts);
+        info
+    }
+
+    /// Renders the debugging information as an HTML fragment
+    pub fn to_html(&self) -> String {
+        let mut output = String::from("<section class=\"debug\"><h2>Debug information</h2>");
+        output.push_str(&format!(
+            "<p><strong>Type:</strong> <code>{}</code></p>",
+            escape_html(&self.type_name)
+        ));
+
+        if let Some(help) = &self.help {
+            output.push_str(&format!(
+                "<p><strong>Help:</strong> {}</p>",
+                escape_html(help)
+            ));
+        }
+
+        if let Some(remediation) = &self.remediation {
+            output.push_str(&format!(
+                "<p><strong>Remediation:</strong> {}</p>",
+                escape_html(remediation)
+            ));
+        }
+
+        output.push_str("<h3>Error chain</h3><ol>");
+        for message in &self.chain {
+            output.push_str(&format!("<li>{}</li>", escape_html(message)));
+        }
+        output.push_str("</ol>");
+
+        if let Some(request) = &self.request {
+            output.push_str(&format!(
+                "<h3>Request</h3><p><code>{} {}</code></p><table>",
+                escape_html(&request.method),
+                escape_html(&request.uri)
+            ));
+            for (name, value) in &request.headers {
+                output.push_str(&format!(
+                    "<tr><th>{}</th><td>{}</td></tr>",
+                    escape_html(name),
+                    escape_html(value)
+                ));
+            }
+            output.push_str("</table>");
+        }
+
+        if let Some(backtrace) = &self.backtrace {
+            output.push_str(&format!(
+                "<h3>Backtrace</h3><pre>{}</pre>",
+                escape_html(backtrace)
+            ));
+        }
+
+        output.push_str("</section>");
+        output
+    }
+}
+
+/// Finds the backtrace for the error if one was captured
+fn find_backtrace(error: &dyn HttpError) -> Option<&Backtrace> {
+    #[cfg(feature = "provide")]
+    {
+        let error: &(dyn std::error::Error + 'static) = error;
+        if let Some(backtrace) = std::error::request_ref::<Backtrace>(error) {
+            return Some(backtrace);
+        }
+    }
+
+    #[cfg(feature = "anyhow")]
+    if let Some(error) = error.downcast_ref::<crate::AnyhowHttpError>() {
+        return Some(error.backtrace());
+    }
+
+    _ = error;
+    None
+}
@@ -0,0 +1,126 @@
+//! Deduplication of error logs within a time window, used to coalesce
+//! storms of identical errors into a single log event with a count. Only
+//! logging goes through the deduplication, sinks and metrics see every error
+
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use http::StatusCode;
+
+use crate::kind::fingerprint;
+use crate::HttpError;
+
+/// Maximum number of fingerprints tracked, the oldest entries are evicted
+/// once reached
+const MAX_ENTRIES: usize = 1024;
+
+/// Tracked fingerprints
+static STATE: Mutex<Option<DedupState>> = Mutex::new(None);
+
+/// Tracked fingerprints and when expired entries were last pruned
+struct DedupState {
+    /// State for each fingerprint
+    entries: HashMap<u64, DedupEntry>,
+    /// When expired entries were last pruned
+    pruned_at: Instant,
+}
+
+/// State for a single fingerprint
+struct DedupEntry {
+    /// When the error was last logged
+    logged_at: Instant,
+    /// Number of errors suppressed since the error was last logged
+    suppressed: u64,
+    /// Type name of the error, for reporting the suppressed count
+    type_name: String,
+    /// Status of the error, for reporting the suppressed count
+    status: StatusCode,
+}
+
+impl DedupEntry {
+    /// Logs the number of suppressed errors if any were suppressed
+    fn flush(&self) {
+        if self.suppressed > 0 {
+            log::warn!(
+                "Suppressed {} duplicate {} errors ({})",
+                self.suppressed,
+                self.type_name,
+                self.status
+            );
+        }
+    }
+}
+
+/// Logs the error unless an error with the same fingerprint was logged
+/// within the window. The number of suppressed errors is logged the next
+/// time the error is logged, or when the entry expires which is checked
+/// whenever any error is logged at least one window after the last check
+pub(crate) fn log_deduplicated(error: &dyn HttpError, window: Duration) {
+    let fingerprint = fingerprint(error);
+    let now = Instant::now();
+
+    let mut expired = Vec::new();
+    let previous = {
+        let mut state = STATE.lock().unwrap_or_else(|err| err.into_inner());
+        let state = state.get_or_insert_with(|| DedupState {
+            entries: HashMap::new(),
+            pruned_at: now,
+        });
+
+        if let Some(entry) = state.entries.get_mut(&fingerprint) {
+            if now.duration_since(entry.logged_at) < window {
+                entry.suppressed += 1;
+                return;
+            }
+        }
+
+        let previous = state.entries.remove(&fingerprint);
+
+        // Expired entries are removed so their suppressed counts are reported
+        if now.duration_since(state.pruned_at) >= window {
+            state.pruned_at = now;
+            state.entries.retain(|_, entry| {
+                let live = now.duration_since(entry.logged_at) < window;
+                if !live && entry.suppressed > 0 {
+                    expired.push(DedupEntry {
+                        type_name: std::mem::take(&mut entry.type_name),
+                        ..*entry
+                    });
+                }
+                live
+            });
+        }
+
+        // Evict the oldest entries when too many errors are live at once
+        while state.entries.len() >= MAX_ENTRIES {
+            let Some(oldest) = state
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.logged_at)
+                .map(|(fingerprint, _)| *fingerprint)
+            else {
+                break;
+            };
+            expired.extend(state.entries.remove(&oldest));
+        }
+
+        state.entries.insert(
+            fingerprint,
+            DedupEntry {
+                logged_at: now,
+                suppressed: 0,
+                type_name: error.type_name().to_string(),
+                status: error.status(),
+            },
+        );
+
+        previous
+    };
+
+    for entry in expired.iter().chain(&previous) {
+        entry.flush();
+    }
+
+    error.log();
+}
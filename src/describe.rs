@@ -0,0 +1,53 @@
+//! Machine readable descriptions of errors
+
+use http::StatusCode;
+
+use crate::{DynHttpError, ErrorKind, HttpError, IntoHttpErrorResponse};
+
+/// Machine readable description of an error for programmatic inspection
+/// (i.e audit logs and admin endpoints) rather than parsing the [Debug]
+/// output of [DynHttpError]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ErrorDescription {
+    /// The full type name of the underlying error
+    pub type_name: String,
+    /// The HTTP status code of the error
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize_status"))]
+    pub status: StatusCode,
+    /// The machine readable error code if the error has one
+    pub code: Option<String>,
+    /// The broad category of the error
+    pub kind: ErrorKind,
+    /// The reason for the error
+    pub reason: String,
+}
+
+impl ErrorDescription {
+    /// Creates a description of the provided error
+    pub fn from_error(error: &dyn HttpError) -> Self {
+        Self {
+            type_name: error.type_name().to_string(),
+            status: error.status(),
+            code: error.code().map(str::to_string),
+            kind: error.kind(),
+            reason: error.reason(),
+        }
+    }
+}
+
+impl<I: IntoHttpErrorResponse> DynHttpError<I> {
+    /// Creates a machine readable description of this error
+    pub fn describe(&self) -> ErrorDescription {
+        ErrorDescription::from_error(self.inner.as_error())
+    }
+}
+
+/// Serializes status codes as their numeric value
+#[cfg(feature = "serde")]
+pub(crate) fn serialize_status<S: serde::Serializer>(
+    status: &StatusCode,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    serializer.serialize_u16(status.as_u16())
+}
@@ -0,0 +1,212 @@
+//! Builder for ad-hoc errors
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::{HeaderMap, HeaderName, HeaderValue, StatusCode};
+
+use crate::{DynHttpError, ErrorKind, HttpError, IntoHttpErrorResponse};
+
+/// Builder for ad-hoc errors with a code, headers and details, for cases
+/// where defining an error type is overkill but [MessageHttpError](crate::MessageHttpError)
+/// is too limited
+///
+/// ```
+/// use axum_dyn_error::{HttpErrorBuilder, HttpResult, StatusCode};
+/// use axum::http::{header::LOCATION, HeaderValue};
+///
+/// pub async fn create_user() -> HttpResult<()> {
+///     Err(HttpErrorBuilder::new(StatusCode::CONFLICT)
+///         .code("DUPLICATE_USER")
+///         .reason("User already exists")
+///         .header(LOCATION, HeaderValue::from_static("/users/42"))
+///         .build())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct HttpErrorBuilder {
+    /// The error being built
+    error: BuiltHttpError,
+}
+
+impl HttpErrorBuilder {
+    /// Creates a new builder for an error with the provided status, the
+    /// reason defaults to the canonical reason for the status
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            error: BuiltHttpError {
+                status,
+                reason: None,
+                message: None,
+                code: None,
+                kind: None,
+                help: None,
+                remediation: None,
+                headers: HeaderMap::new(),
+                #[cfg(feature = "json")]
+                details: serde_json::Map::new(),
+            },
+        }
+    }
+
+    /// Sets the reason included in the response
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.error.reason = Some(reason.into());
+        self
+    }
+
+    /// Sets the message only included in logs, defaults to the reason
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.error.message = Some(message.into());
+        self
+    }
+
+    /// Sets the machine readable error code (See [HttpError::code])
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.error.code = Some(code.into());
+        self
+    }
+
+    /// Sets the kind of the error, defaults to the kind for the status
+    pub fn kind(mut self, kind: ErrorKind) -> Self {
+        self.error.kind = Some(kind);
+        self
+    }
+
+    /// Sets the advice for the client on how to resolve the error
+    /// (See [HttpError::help])
+    pub fn help(mut self, help: impl Into<String>) -> Self {
+        self.error.help = Some(help.into());
+        self
+    }
+
+    /// Sets the steps for remediating the error (See [HttpError::remediation])
+    pub fn remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.error.remediation = Some(remediation.into());
+        self
+    }
+
+    /// Adds a header to the response, replacing any previous value
+    pub fn header(mut self, name: HeaderName, value: HeaderValue) -> Self {
+        self.error.headers.insert(name, value);
+        self
+    }
+
+    /// Adds a key-value pair to the error details, values that fail to
+    /// serialize are ignored
+    ///
+    /// ```
+    /// use axum_dyn_error::{DynHttpError, HttpErrorBuilder, StatusCode};
+    ///
+    /// let existing_id = 42;
+    /// let error: DynHttpError = HttpErrorBuilder::new(StatusCode::CONFLICT)
+    ///     .code("DUPLICATE_USER")
+    ///     .detail("id", existing_id)
+    ///     .build();
+    /// ```
+    #[cfg(feature = "json")]
+    pub fn detail(mut self, key: impl Into<String>, value: impl serde::Serialize) -> Self {
+        if let Ok(value) = serde_json::to_value(value) {
+            self.error.details.insert(key.into(), value);
+        }
+        self
+    }
+
+    /// Creates the error without converting it into a [DynHttpError]
+    pub fn into_error(self) -> BuiltHttpError {
+        self.error
+    }
+
+    /// Creates the error as a [DynHttpError]
+    #[track_caller]
+    pub fn build<I: IntoHttpErrorResponse>(self) -> DynHttpError<I> {
+        self.error.into()
+    }
+}
+
+/// Error created by a [HttpErrorBuilder]
+#[derive(Debug, Clone)]
+pub struct BuiltHttpError {
+    /// The status code of the error
+    status: StatusCode,
+    /// The reason included in the response
+    reason: Option<String>,
+    /// The message only included in logs
+    message: Option<String>,
+    /// The machine readable error code
+    code: Option<String>,
+    /// The kind of the error
+    kind: Option<ErrorKind>,
+    /// Advice on how to resolve the error
+    help: Option<String>,
+    /// Steps for remediating the error
+    remediation: Option<String>,
+    /// Headers included in the response
+    headers: HeaderMap,
+    /// Structured details of the error
+    #[cfg(feature = "json")]
+    details: serde_json::Map<String, serde_json::Value>,
+}
+
+impl Display for BuiltHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match (&self.message, &self.reason) {
+            (Some(message), _) | (None, Some(message)) => f.write_str(message),
+            (None, None) => f.write_str(crate::reason_phrase(self.status)),
+        }
+    }
+}
+
+impl Error for BuiltHttpError {}
+
+impl HttpError for BuiltHttpError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status.is_client_error() {
+            log::debug!("{self}");
+        } else {
+            log::error!("{self}{}", crate::location::log_suffix());
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn reason(&self) -> String {
+        match &self.reason {
+            Some(reason) => reason.clone(),
+            None => crate::reason_phrase(self.status).to_string(),
+        }
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        headers.extend(self.headers.clone());
+    }
+
+    fn code(&self) -> Option<&str> {
+        self.code.as_deref()
+    }
+
+    fn help(&self) -> Option<String> {
+        self.help.clone()
+    }
+
+    fn remediation(&self) -> Option<String> {
+        self.remediation.clone()
+    }
+
+    fn kind(&self) -> ErrorKind {
+        self.kind
+            .unwrap_or_else(|| ErrorKind::from_status(self.status))
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        if self.details.is_empty() {
+            return None;
+        }
+
+        Some(serde_json::Value::Object(self.details.clone()))
+    }
+}
@@ -0,0 +1,213 @@
+//! Errors for optimistic concurrency and conditional requests
+
+use std::error::Error;
+use std::fmt::Display;
+use std::time::SystemTime;
+
+use http::{
+    header::{ETAG, LAST_MODIFIED},
+    HeaderMap, HeaderValue, StatusCode,
+};
+
+use crate::HttpError;
+
+/// Error for requests that conflict with the current state of the
+/// resource (409 Conflict), optionally carrying the current ETag of
+/// the resource so clients can retry against the latest version
+#[derive(Debug)]
+pub struct Conflict {
+    /// The reason for the conflict
+    reason: String,
+    /// The current ETag of the resource
+    etag: Option<String>,
+}
+
+impl Conflict {
+    /// Creates a new conflict error with the provided reason
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            etag: None,
+        }
+    }
+
+    /// Sets the current ETag of the resource, this should be the full
+    /// ETag header value including quotes (i.e `"v2"` or `W/"v2"`)
+    pub fn etag(mut self, etag: impl Into<String>) -> Self {
+        self.etag = Some(etag.into());
+        self
+    }
+}
+
+impl Display for Conflict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl Error for Conflict {}
+
+impl HttpError for Conflict {
+    fn status(&self) -> StatusCode {
+        StatusCode::CONFLICT
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        insert_etag(headers, self.etag.as_deref());
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        etag_details(self.etag.as_deref())
+    }
+}
+
+/// Response for conditional GET requests where the resource hasn't changed
+/// since the version the client has cached (304 Not Modified), allowing
+/// handlers to `?` return the response when a `If-None-Match` or
+/// `If-Modified-Since` condition matches.
+///
+/// The response is sent without a body and includes the validators
+/// (`ETag` and `Last-Modified`) of the resource
+#[derive(Debug, Clone, Default)]
+pub struct NotModified {
+    /// The current ETag of the resource
+    etag: Option<String>,
+    /// When the resource was last modified
+    last_modified: Option<SystemTime>,
+}
+
+impl NotModified {
+    /// Creates a new not modified response without any validators
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the current ETag of the resource, this should be the full
+    /// ETag header value including quotes (i.e `"v2"` or `W/"v2"`)
+    pub fn etag(mut self, etag: impl Into<String>) -> Self {
+        self.etag = Some(etag.into());
+        self
+    }
+
+    /// Sets when the resource was last modified
+    pub fn last_modified(mut self, last_modified: SystemTime) -> Self {
+        self.last_modified = Some(last_modified);
+        self
+    }
+}
+
+impl Display for NotModified {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Not modified")
+    }
+}
+
+impl Error for NotModified {}
+
+impl HttpError for NotModified {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        // Cache validation is expected behavior so it isn't logged as an error
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::NOT_MODIFIED
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        insert_etag(headers, self.etag.as_deref());
+        insert_last_modified(headers, self.last_modified);
+    }
+}
+
+/// Error for requests where a precondition such as `If-Match` did not
+/// match the current state of the resource (412 Precondition Failed),
+/// optionally carrying the current validators of the resource.
+///
+/// Like [NotModified] the response is sent without a body (See
+/// [HttpError::bodiless]), the reason is only included in logs and sinks
+#[derive(Debug)]
+pub struct PreconditionFailed {
+    /// The reason the precondition failed
+    reason: String,
+    /// The current ETag of the resource
+    etag: Option<String>,
+    /// When the resource was last modified
+    last_modified: Option<SystemTime>,
+}
+
+impl PreconditionFailed {
+    /// Creates a new precondition failed error with the provided reason
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            etag: None,
+            last_modified: None,
+        }
+    }
+
+    /// Sets the current ETag of the resource, this should be the full
+    /// ETag header value including quotes (i.e `"v2"` or `W/"v2"`)
+    pub fn etag(mut self, etag: impl Into<String>) -> Self {
+        self.etag = Some(etag.into());
+        self
+    }
+
+    /// Sets when the resource was last modified
+    pub fn last_modified(mut self, last_modified: SystemTime) -> Self {
+        self.last_modified = Some(last_modified);
+        self
+    }
+}
+
+impl Display for PreconditionFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl Error for PreconditionFailed {}
+
+impl HttpError for PreconditionFailed {
+    fn status(&self) -> StatusCode {
+        StatusCode::PRECONDITION_FAILED
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        insert_etag(headers, self.etag.as_deref());
+        insert_last_modified(headers, self.last_modified);
+    }
+
+    fn bodiless(&self) -> bool {
+        true
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        etag_details(self.etag.as_deref())
+    }
+}
+
+/// Inserts the ETag header if the ETag is present and valid
+fn insert_etag(headers: &mut HeaderMap, etag: Option<&str>) {
+    if let Some(value) = etag.and_then(|etag| HeaderValue::from_str(etag).ok()) {
+        headers.insert(ETAG, value);
+    }
+}
+
+/// Inserts the Last-Modified header if the time is present
+fn insert_last_modified(headers: &mut HeaderMap, last_modified: Option<SystemTime>) {
+    if let Some(value) =
+        last_modified.and_then(|time| HeaderValue::from_str(&httpdate::fmt_http_date(time)).ok())
+    {
+        headers.insert(LAST_MODIFIED, value);
+    }
+}
+
+/// Creates the details object containing the ETag
+#[cfg(feature = "json")]
+fn etag_details(etag: Option<&str>) -> Option<serde_json::Value> {
+    etag.map(|etag| serde_json::json!({ "etag": etag }))
+}
@@ -0,0 +1,169 @@
+//! Errors and headers for signaling API deprecation
+
+use std::error::Error;
+use std::fmt::Display;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use http::{
+    header::{HeaderName, LINK, WARNING},
+    HeaderMap, HeaderValue, StatusCode,
+};
+
+use crate::HttpError;
+
+/// The `Deprecation` header (RFC 9745)
+pub const DEPRECATION: HeaderName = HeaderName::from_static("deprecation");
+
+/// The `Sunset` header (RFC 8594)
+pub const SUNSET: HeaderName = HeaderName::from_static("sunset");
+
+/// Machine readable deprecation information that can be included in
+/// the headers of a response using the `Deprecation`, `Sunset`, `Link`
+/// and `Warning` headers. Errors can include these headers using
+/// [Deprecation::insert_headers] from [HttpError::headers]
+#[derive(Debug, Clone, Default)]
+pub struct Deprecation {
+    /// When the resource was deprecated
+    deprecated_at: Option<SystemTime>,
+    /// When the resource will be / was removed
+    sunset: Option<SystemTime>,
+    /// Link to documentation about the deprecation
+    link: Option<String>,
+    /// Human readable warning message
+    warning: Option<String>,
+}
+
+impl Deprecation {
+    /// Creates empty deprecation information
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets when the resource was deprecated
+    pub fn deprecated_at(mut self, time: SystemTime) -> Self {
+        self.deprecated_at = Some(time);
+        self
+    }
+
+    /// Sets when the resource will be or was removed
+    pub fn sunset(mut self, time: SystemTime) -> Self {
+        self.sunset = Some(time);
+        self
+    }
+
+    /// Sets a link to documentation about the deprecation
+    pub fn link(mut self, link: impl Into<String>) -> Self {
+        self.link = Some(link.into());
+        self
+    }
+
+    /// Sets a human readable warning included in the `Warning` header
+    pub fn warning(mut self, warning: impl Into<String>) -> Self {
+        self.warning = Some(warning.into());
+        self
+    }
+
+    /// Inserts the deprecation headers into the provided headers
+    pub fn insert_headers(&self, headers: &mut HeaderMap) {
+        match self.deprecated_at {
+            // Deprecation uses the structured field date format (@<unix seconds>)
+            Some(time) => {
+                let seconds = time
+                    .duration_since(UNIX_EPOCH)
+                    .map(|duration| duration.as_secs())
+                    .unwrap_or_default();
+                if let Ok(value) = HeaderValue::from_str(&format!("@{seconds}")) {
+                    headers.insert(DEPRECATION, value);
+                }
+            }
+            None => {
+                headers.insert(DEPRECATION, HeaderValue::from_static("true"));
+            }
+        }
+
+        if let Some(sunset) = self.sunset {
+            if let Ok(value) = HeaderValue::from_str(&httpdate::fmt_http_date(sunset)) {
+                headers.insert(SUNSET, value);
+            }
+        }
+
+        if let Some(link) = &self.link {
+            if let Ok(value) = HeaderValue::from_str(&format!("<{link}>; rel=\"deprecation\"")) {
+                headers.append(LINK, value);
+            }
+        }
+
+        if let Some(warning) = &self.warning {
+            let warning = warning.replace('"', "'");
+            if let Ok(value) = HeaderValue::from_str(&format!("299 - \"{warning}\"")) {
+                headers.insert(WARNING, value);
+            }
+        }
+    }
+
+    /// Creates the details object describing the deprecation
+    #[cfg(feature = "json")]
+    fn details(&self) -> serde_json::Value {
+        serde_json::json!({
+            "deprecated_at": self.deprecated_at.map(httpdate::fmt_http_date),
+            "sunset": self.sunset.map(httpdate::fmt_http_date),
+            "link": self.link,
+        })
+    }
+}
+
+/// Error for endpoints that have been deprecated and removed (410 Gone)
+/// including the deprecation headers
+#[derive(Debug, Clone)]
+pub struct GoneDeprecated {
+    /// The reason for the error
+    reason: String,
+    /// The deprecation information
+    deprecation: Deprecation,
+}
+
+impl GoneDeprecated {
+    /// Creates a new error for an endpoint that was removed at the
+    /// provided sunset time
+    pub fn new(sunset: SystemTime) -> Self {
+        Self {
+            reason: "This endpoint has been removed".to_string(),
+            deprecation: Deprecation::new().sunset(sunset),
+        }
+    }
+
+    /// Sets the reason for the error
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = reason.into();
+        self
+    }
+
+    /// Sets the deprecation information
+    pub fn deprecation(mut self, deprecation: Deprecation) -> Self {
+        self.deprecation = deprecation;
+        self
+    }
+}
+
+impl Display for GoneDeprecated {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl Error for GoneDeprecated {}
+
+impl HttpError for GoneDeprecated {
+    fn status(&self) -> StatusCode {
+        StatusCode::GONE
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        self.deprecation.insert_headers(headers);
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        Some(self.deprecation.details())
+    }
+}
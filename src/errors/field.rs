@@ -0,0 +1,132 @@
+//! Wrapper for errors caused by the value of a specific field
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::{HeaderMap, StatusCode};
+
+use crate::{ErrorKind, HttpError};
+
+/// Error wrapping another [HttpError] with the name of the field whose
+/// value caused it, such as a path parameter that isn't a valid ID. The
+/// field is included in the reason and the error details
+///
+/// ```
+/// use axum_dyn_error::{FieldResultExt, HttpResult, MessageHttpError, StatusCode};
+///
+/// fn parse_page(page: &str) -> Result<u32, MessageHttpError> {
+///     page.parse()
+///         .map_err(|_| MessageHttpError::new(StatusCode::BAD_REQUEST, "expected a number"))
+/// }
+///
+/// pub async fn list(page: String) -> HttpResult<String> {
+///     let page = parse_page(&page).for_field("page")?;
+///     Ok(format!("Page {page}"))
+/// }
+/// ```
+#[derive(Debug)]
+pub struct InvalidField<E> {
+    /// Name of the field
+    field: String,
+    /// The error caused by the value of the field
+    error: E,
+}
+
+impl<E> InvalidField<E> {
+    /// Creates a new error for the field with the provided name
+    pub fn new(field: impl Into<String>, error: E) -> Self {
+        Self {
+            field: field.into(),
+            error,
+        }
+    }
+
+    /// Name of the field
+    pub fn field(&self) -> &str {
+        &self.field
+    }
+
+    /// Unwraps the error caused by the value of the field
+    pub fn into_inner(self) -> E {
+        self.error
+    }
+}
+
+impl<E: Display> Display for InvalidField<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Invalid {}: {}", self.field, self.error)
+    }
+}
+
+impl<E: Error + 'static> Error for InvalidField<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<E: HttpError> HttpError for InvalidField<E> {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_client_error() {
+            log::debug!("{self}");
+        } else {
+            self.error.log();
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        self.error.status()
+    }
+
+    fn reason(&self) -> String {
+        if self.status().is_client_error() {
+            format!("Invalid {}: {}", self.field, self.error.reason())
+        } else {
+            self.error.reason()
+        }
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        self.error.headers(headers)
+    }
+
+    fn code(&self) -> Option<&str> {
+        self.error.code()
+    }
+
+    fn help(&self) -> Option<String> {
+        self.error.help()
+    }
+
+    fn remediation(&self) -> Option<String> {
+        self.error.remediation()
+    }
+
+    fn kind(&self) -> ErrorKind {
+        self.error.kind()
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        let mut details = serde_json::json!({ "field": self.field });
+        if let Some(serde_json::Value::Object(inner)) = self.error.details() {
+            if let Some(object) = details.as_object_mut() {
+                object.extend(inner);
+            }
+        }
+        Some(details)
+    }
+}
+
+/// Extension for capturing the field that caused the error of a result
+pub trait FieldResultExt<T, E> {
+    /// Wraps the error in an [InvalidField] error for the field with the
+    /// provided name
+    fn for_field(self, field: impl Into<String>) -> Result<T, InvalidField<E>>;
+}
+
+impl<T, E: HttpError> FieldResultExt<T, E> for Result<T, E> {
+    fn for_field(self, field: impl Into<String>) -> Result<T, InvalidField<E>> {
+        self.map_err(|error| InvalidField::new(field, error))
+    }
+}
@@ -0,0 +1,203 @@
+//! Errors for readiness and liveness health checks
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::StatusCode;
+
+use crate::HttpError;
+
+/// Status of a single component of a health check, such as a database
+/// connection or a downstream service
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ComponentStatus {
+    /// Name of the component
+    pub name: String,
+    /// Whether the component is healthy
+    pub healthy: bool,
+    /// Message describing the status of the component
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub message: Option<String>,
+}
+
+impl ComponentStatus {
+    /// Creates the status of a healthy component
+    pub fn up(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            healthy: true,
+            message: None,
+        }
+    }
+
+    /// Creates the status of an unhealthy component
+    pub fn down(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            healthy: false,
+            message: None,
+        }
+    }
+
+    /// Sets the message describing the status of the component
+    pub fn message(mut self, message: impl Into<String>) -> Self {
+        self.message = Some(message.into());
+        self
+    }
+}
+
+/// Formats the names of the unhealthy components
+fn unhealthy_names(components: &[ComponentStatus]) -> String {
+    components
+        .iter()
+        .filter(|component| !component.healthy)
+        .map(|component| component.name.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Creates the details listing the status of the components
+#[cfg(feature = "json")]
+fn components_details(components: &[ComponentStatus]) -> Option<serde_json::Value> {
+    serde_json::to_value(components)
+        .ok()
+        .map(|components| serde_json::json!({ "components": components }))
+}
+
+/// Error for readiness checks when the service isn't ready to receive
+/// traffic yet (503 Service Unavailable), such as while warming caches or
+/// waiting for a dependency. The status of each component is included in
+/// the details
+///
+/// ```
+/// use axum_dyn_error::{ComponentStatus, HttpResult, NotReady};
+///
+/// pub async fn ready() -> HttpResult<&'static str> {
+///     let database = ComponentStatus::down("database").message("connecting");
+///     let cache = ComponentStatus::up("cache");
+///
+///     if !database.healthy {
+///         return Err(NotReady::new().component(database).component(cache).into());
+///     }
+///
+///     Ok("ready")
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct NotReady {
+    /// Status of the checked components
+    components: Vec<ComponentStatus>,
+}
+
+impl NotReady {
+    /// Creates a new error without any components
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the status of a checked component
+    pub fn component(mut self, status: ComponentStatus) -> Self {
+        self.components.push(status);
+        self
+    }
+
+    /// Status of the checked components
+    pub fn components(&self) -> &[ComponentStatus] {
+        &self.components
+    }
+}
+
+impl Display for NotReady {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Service not ready")?;
+        let unhealthy = unhealthy_names(&self.components);
+        if !unhealthy.is_empty() {
+            write!(f, " ({unhealthy})")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for NotReady {}
+
+impl HttpError for NotReady {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        // Services are expected to be unready while starting
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+
+    fn reason(&self) -> String {
+        "Service not ready".to_string()
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        components_details(&self.components)
+    }
+}
+
+/// Error for liveness checks when the service is unhealthy and should be
+/// restarted (503 Service Unavailable). The status of each component is
+/// included in the details
+#[derive(Debug, Clone, Default)]
+pub struct Unhealthy {
+    /// Status of the checked components
+    components: Vec<ComponentStatus>,
+}
+
+impl Unhealthy {
+    /// Creates a new error without any components
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the status of a checked component
+    pub fn component(mut self, status: ComponentStatus) -> Self {
+        self.components.push(status);
+        self
+    }
+
+    /// Status of the checked components
+    pub fn components(&self) -> &[ComponentStatus] {
+        &self.components
+    }
+}
+
+impl Display for Unhealthy {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Service unhealthy")?;
+        let unhealthy = unhealthy_names(&self.components);
+        if !unhealthy.is_empty() {
+            write!(f, " ({unhealthy})")?;
+        }
+        Ok(())
+    }
+}
+
+impl Error for Unhealthy {}
+
+impl HttpError for Unhealthy {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        log::error!("{self}{}", crate::location::log_suffix());
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+
+    fn reason(&self) -> String {
+        "Service unhealthy".to_string()
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        components_details(&self.components)
+    }
+}
@@ -0,0 +1,123 @@
+//! Errors created from static messages
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::StatusCode;
+
+use crate::{DynHttpError, HttpError, IntoHttpErrorResponse};
+
+/// Error created from a status code and a static message, these can be
+/// created in const contexts and converted into a [DynHttpError] using
+/// [StaticHttpError::into_dyn]. Construction doesn't allocate, converting
+/// the error into a response only boxes it when a rewrite applies or the
+/// formatter doesn't support static errors (See [DynHttpError::from_static])
+///
+/// ```
+/// use axum_dyn_error::{DynHttpError, StaticHttpError, StatusCode};
+///
+/// const USER_NOT_FOUND: StaticHttpError =
+///     StaticHttpError::new(StatusCode::NOT_FOUND, "User not found");
+///
+/// let error: DynHttpError = USER_NOT_FOUND.into_dyn();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StaticHttpError {
+    /// The status code of the error
+    status: StatusCode,
+    /// The error message
+    message: &'static str,
+}
+
+impl StaticHttpError {
+    /// Creates a new static error
+    pub const fn new(status: StatusCode, message: &'static str) -> Self {
+        Self { status, message }
+    }
+
+    /// The error message
+    pub const fn message(&self) -> &'static str {
+        self.message
+    }
+
+    /// Converts this error into a [DynHttpError], the conversion doesn't
+    /// allocate (See [DynHttpError::from_static])
+    #[track_caller]
+    pub const fn into_dyn<I: IntoHttpErrorResponse>(self) -> DynHttpError<I> {
+        DynHttpError::from_static_error(self)
+    }
+}
+
+impl Display for StaticHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.message)
+    }
+}
+
+impl Error for StaticHttpError {}
+
+impl HttpError for StaticHttpError {
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+}
+
+/// Error created from a status code and a message, used by the semantic
+/// constructors on [DynHttpError] such as [DynHttpError::not_found]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MessageHttpError {
+    /// The status code of the error
+    status: StatusCode,
+    /// The error message
+    message: String,
+    /// Whether the message should be hidden from the response
+    hidden: bool,
+}
+
+impl MessageHttpError {
+    /// Creates a new error using the message as the response reason
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            hidden: false,
+        }
+    }
+
+    /// Creates a new error where the message is only included in logs and
+    /// the response uses a generic reason
+    pub fn hidden(status: StatusCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+            hidden: true,
+        }
+    }
+
+    /// The error message
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl Display for MessageHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for MessageHttpError {}
+
+impl HttpError for MessageHttpError {
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn reason(&self) -> String {
+        if self.hidden {
+            return "Server error".to_string();
+        }
+
+        self.message.clone()
+    }
+}
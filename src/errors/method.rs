@@ -0,0 +1,84 @@
+//! Error for requests using a method the resource doesn't support
+
+use std::error::Error;
+use std::fmt::Display;
+use std::future::{ready, Ready};
+
+use http::{header::ALLOW, HeaderMap, HeaderValue, Method, StatusCode};
+
+use crate::{DynHttpError, HttpError, IntoHttpErrorResponse};
+
+/// Error for requests using a method that the resource doesn't support
+/// (405 Method Not Allowed), the allowed methods are listed in the `Allow`
+/// header of the response as required by the specification
+#[derive(Debug, Clone)]
+pub struct MethodNotAllowed {
+    /// The methods allowed by the resource
+    pub allowed: Vec<Method>,
+}
+
+impl MethodNotAllowed {
+    /// Creates a new error listing the provided allowed methods
+    pub fn new(allowed: impl Into<Vec<Method>>) -> Self {
+        Self {
+            allowed: allowed.into(),
+        }
+    }
+
+    /// Creates a handler that responds with this error, intended to be
+    /// used as the fallback of an axum method router:
+    ///
+    /// ```
+    /// use axum::{routing::get, Router, http::Method};
+    /// use axum_dyn_error::{MethodNotAllowed, TextErrorResponse};
+    ///
+    /// let app: Router = Router::new().route(
+    ///     "/",
+    ///     get(|| async { "Hello" }).fallback(
+    ///         MethodNotAllowed::new([Method::GET, Method::HEAD]).fallback::<TextErrorResponse>(),
+    ///     ),
+    /// );
+    /// ```
+    pub fn fallback<I>(
+        self,
+    ) -> impl FnOnce() -> Ready<DynHttpError<I>> + Clone + Send + Sync + 'static
+    where
+        I: IntoHttpErrorResponse + 'static,
+    {
+        move || ready(DynHttpError::from(self))
+    }
+}
+
+impl Display for MethodNotAllowed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Method not allowed")
+    }
+}
+
+impl Error for MethodNotAllowed {}
+
+impl HttpError for MethodNotAllowed {
+    fn status(&self) -> StatusCode {
+        StatusCode::METHOD_NOT_ALLOWED
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        let allowed = self
+            .allowed
+            .iter()
+            .map(Method::as_str)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if let Ok(value) = HeaderValue::from_str(&allowed) {
+            headers.insert(ALLOW, value);
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        let allowed = self.allowed.iter().map(Method::as_str).collect::<Vec<_>>();
+
+        Some(serde_json::json!({ "allowed": allowed }))
+    }
+}
@@ -0,0 +1,37 @@
+//! Common error types for HTTP responses
+
+mod builder;
+mod conditional;
+mod deprecated;
+mod field;
+mod health;
+mod message;
+mod method;
+mod multi;
+mod panic;
+mod payload;
+mod redirect;
+#[cfg(feature = "axum")]
+mod response;
+mod session;
+mod shutdown;
+mod unavailable;
+mod upstream;
+
+pub use builder::*;
+pub use conditional::*;
+pub use deprecated::*;
+pub use field::*;
+pub use health::*;
+pub use message::*;
+pub use method::*;
+pub use multi::*;
+pub use panic::*;
+pub use payload::*;
+pub use redirect::*;
+#[cfg(feature = "axum")]
+pub use response::*;
+pub use session::*;
+pub use shutdown::*;
+pub use unavailable::*;
+pub use upstream::*;
@@ -0,0 +1,265 @@
+//! Error made up of multiple individual errors
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::StatusCode;
+
+use crate::HttpError;
+
+/// Single entry within a [MultiHttpError], such as an invalid field or
+/// a failed line of an import
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ErrorEntry {
+    /// The index of the item the error occurred for within a batch
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub index: Option<u64>,
+    /// The line the error occurred on
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub line: Option<u64>,
+    /// The column the error occurred on
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub column: Option<u64>,
+    /// The field the error is for
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub field: Option<String>,
+    /// Machine readable code for the error
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub code: Option<String>,
+    /// The error message
+    pub message: String,
+}
+
+impl ErrorEntry {
+    /// Creates a new entry with the provided message
+    pub fn new(message: impl Into<String>) -> Self {
+        Self {
+            index: None,
+            line: None,
+            column: None,
+            field: None,
+            code: None,
+            message: message.into(),
+        }
+    }
+
+    /// Creates an entry from the reason and code of the provided error
+    pub fn from_error(error: &dyn HttpError) -> Self {
+        Self {
+            code: error.code().map(str::to_string),
+            ..Self::new(error.reason())
+        }
+    }
+
+    /// Sets the index of the item the error occurred for within a batch
+    pub fn index(mut self, index: u64) -> Self {
+        self.index = Some(index);
+        self
+    }
+
+    /// Sets the line the error occurred on
+    pub fn line(mut self, line: u64) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// Sets the column the error occurred on
+    pub fn column(mut self, column: u64) -> Self {
+        self.column = Some(column);
+        self
+    }
+
+    /// Sets the field the error is for
+    pub fn field(mut self, field: impl Into<String>) -> Self {
+        self.field = Some(field.into());
+        self
+    }
+
+    /// Sets the machine readable code for the error
+    pub fn code(mut self, code: impl Into<String>) -> Self {
+        self.code = Some(code.into());
+        self
+    }
+}
+
+/// Summary of the [ErrorEntry]s for a single field
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct FieldSummary {
+    /// The field the errors are for, [None] for entries without a field
+    pub field: Option<String>,
+    /// Number of entries for the field
+    pub count: usize,
+    /// The distinct messages of the entries for the field
+    pub messages: Vec<String>,
+}
+
+/// Summary of the [ErrorEntry]s of a [MultiHttpError] grouped by field
+/// (See [MultiHttpError::details_summary])
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DetailsSummary {
+    /// Total number of entries
+    pub total: usize,
+    /// Summaries for each field in the order they first appeared
+    pub fields: Vec<FieldSummary>,
+}
+
+/// Error made up of multiple [ErrorEntry]s such as validation errors for
+/// multiple fields or the failed lines of a batch import, responds with
+/// 422 Unprocessable Entity by default
+#[derive(Debug, Clone)]
+pub struct MultiHttpError {
+    /// The status code for the error
+    status: StatusCode,
+    /// The reason for the error
+    reason: String,
+    /// The individual errors
+    entries: Vec<ErrorEntry>,
+    /// Number of entries after which the details are collapsed into a summary
+    collapse_threshold: Option<usize>,
+}
+
+impl Default for MultiHttpError {
+    fn default() -> Self {
+        Self {
+            status: StatusCode::UNPROCESSABLE_ENTITY,
+            reason: "Request contained errors".to_string(),
+            entries: Vec::new(),
+            collapse_threshold: None,
+        }
+    }
+}
+
+impl MultiHttpError {
+    /// Creates a new empty error
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the status code of the error
+    pub fn status(mut self, status: StatusCode) -> Self {
+        self.status = status;
+        self
+    }
+
+    /// Sets the reason for the error
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = reason.into();
+        self
+    }
+
+    /// Sets the number of entries after which the details are collapsed
+    /// into a [DetailsSummary] instead of including every entry
+    pub fn collapse_threshold(mut self, threshold: usize) -> Self {
+        self.collapse_threshold = Some(threshold);
+        self
+    }
+
+    /// Adds an entry to the error
+    pub fn with(mut self, entry: ErrorEntry) -> Self {
+        self.entries.push(entry);
+        self
+    }
+
+    /// Adds an entry to the error
+    pub fn push(&mut self, entry: ErrorEntry) {
+        self.entries.push(entry);
+    }
+
+    /// The individual errors
+    pub fn entries(&self) -> &[ErrorEntry] {
+        &self.entries
+    }
+
+    /// The number of individual errors
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether there are no individual errors
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Whether the details are collapsed into a summary because the number
+    /// of entries exceeds the [MultiHttpError::collapse_threshold]
+    pub fn is_collapsed(&self) -> bool {
+        self.collapse_threshold
+            .is_some_and(|threshold| self.entries.len() > threshold)
+    }
+
+    /// Summarizes the entries by grouping them by field with the number of
+    /// entries and distinct messages for each field
+    pub fn details_summary(&self) -> DetailsSummary {
+        let mut fields: Vec<FieldSummary> = Vec::new();
+        for entry in &self.entries {
+            let summary = match fields
+                .iter_mut()
+                .find(|summary| summary.field == entry.field)
+            {
+                Some(summary) => summary,
+                None => {
+                    fields.push(FieldSummary {
+                        field: entry.field.clone(),
+                        count: 0,
+                        messages: Vec::new(),
+                    });
+                    fields.last_mut().expect("summary was just added")
+                }
+            };
+
+            summary.count += 1;
+            if !summary.messages.contains(&entry.message) {
+                summary.messages.push(entry.message.clone());
+            }
+        }
+
+        DetailsSummary {
+            total: self.entries.len(),
+            fields,
+        }
+    }
+}
+
+impl Extend<ErrorEntry> for MultiHttpError {
+    fn extend<T: IntoIterator<Item = ErrorEntry>>(&mut self, iter: T) {
+        self.entries.extend(iter)
+    }
+}
+
+impl FromIterator<ErrorEntry> for MultiHttpError {
+    fn from_iter<T: IntoIterator<Item = ErrorEntry>>(iter: T) -> Self {
+        let mut error = Self::new();
+        error.extend(iter);
+        error
+    }
+}
+
+impl Display for MultiHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} ({} errors)", self.reason, self.entries.len())
+    }
+}
+
+impl Error for MultiHttpError {}
+
+impl HttpError for MultiHttpError {
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn reason(&self) -> String {
+        self.reason.clone()
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        if self.is_collapsed() {
+            return serde_json::to_value(self.details_summary()).ok();
+        }
+
+        serde_json::to_value(&self.entries).ok()
+    }
+}
@@ -0,0 +1,210 @@
+//! Error for panics caught while handling a request
+
+use std::any::Any;
+use std::error::Error;
+use std::fmt::Display;
+
+use http::StatusCode;
+
+use crate::kind::StableHasher;
+use crate::HttpError;
+
+/// Category of a caught panic determined from its payload, panics of
+/// the same category share the same error code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PanicKind {
+    /// Failed `assert!`, `assert_eq!` or `assert_ne!`
+    Assertion,
+    /// `unwrap` or `unwrap_err` on a `None` or unexpected `Result` variant
+    Unwrap,
+    /// Out of bounds index or range of a slice or string
+    IndexOutOfBounds,
+    /// Arithmetic overflow or division by zero
+    Arithmetic,
+    /// Reached `unreachable!`
+    Unreachable,
+    /// Reached `todo!` or `unimplemented!`
+    Unimplemented,
+    /// Any other panic with a string message (i.e `panic!` or `expect`)
+    Message,
+    /// Panic with a payload that isn't a string
+    Opaque,
+}
+
+impl PanicKind {
+    /// Classifies a panic from its message
+    fn classify(message: &str) -> Self {
+        if message.starts_with("assertion failed") || message.starts_with("assertion `") {
+            Self::Assertion
+        } else if message.starts_with("called `Option::unwrap")
+            || message.starts_with("called `Result::unwrap")
+        {
+            Self::Unwrap
+        } else if message.starts_with("index out of bounds")
+            || message.starts_with("range start index")
+            || message.starts_with("range end index")
+            || message.starts_with("slice index starts at")
+            || message.starts_with("byte index")
+        {
+            Self::IndexOutOfBounds
+        } else if message.starts_with("attempt to ") {
+            Self::Arithmetic
+        } else if message.starts_with("internal error: entered unreachable code") {
+            Self::Unreachable
+        } else if message.starts_with("not implemented")
+            || message.starts_with("not yet implemented")
+        {
+            Self::Unimplemented
+        } else {
+            Self::Message
+        }
+    }
+
+    /// Error code used for panics of this category
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::Assertion => "panic_assertion",
+            Self::Unwrap => "panic_unwrap",
+            Self::IndexOutOfBounds => "panic_index_out_of_bounds",
+            Self::Arithmetic => "panic_arithmetic",
+            Self::Unreachable => "panic_unreachable",
+            Self::Unimplemented => "panic_unimplemented",
+            Self::Message => "panic_message",
+            Self::Opaque => "panic",
+        }
+    }
+}
+
+/// Normalizes a panic message so that panics from the same place share
+/// the same reason, only the first line is kept (dropping the `left` and
+/// `right` values of assertions), the values of unwrapped errors are
+/// removed and numbers are replaced with `N`
+fn normalize(kind: PanicKind, message: &str) -> String {
+    let mut message = message.lines().next().unwrap_or_default();
+    if kind == PanicKind::Unwrap {
+        if let Some((prefix, _value)) = message.split_once(" value: ") {
+            message = prefix;
+        }
+    }
+
+    let mut normalized = String::with_capacity(message.len());
+    let mut in_number = false;
+    for char in message.chars() {
+        if char.is_ascii_digit() {
+            if !in_number {
+                normalized.push('N');
+            }
+            in_number = true;
+        } else {
+            normalized.push(char);
+            in_number = false;
+        }
+    }
+    normalized
+}
+
+/// Error for a panic caught while handling a request (500 Internal Server
+/// Error). The panic payload is classified into a [PanicKind] which is
+/// used as the error code so that hooks and sinks group panics by their
+/// category rather than a single bucket, the normalized message is used
+/// as the internal reason and only included in logs
+///
+/// ```
+/// use axum_dyn_error::{PanicError, PanicKind};
+///
+/// let payload = std::panic::catch_unwind(|| {
+///     let values: Vec<u32> = Vec::new();
+///     values[3]
+/// })
+/// .unwrap_err();
+///
+/// let error = PanicError::from_payload(payload.as_ref());
+/// assert_eq!(error.panic_kind(), PanicKind::IndexOutOfBounds);
+/// assert_eq!(
+///     error.message(),
+///     Some("index out of bounds: the len is N but the index is N")
+/// );
+/// ```
+#[derive(Debug, Clone)]
+pub struct PanicError {
+    /// Category of the panic
+    kind: PanicKind,
+    /// Normalized panic message if the payload was a string
+    message: Option<String>,
+}
+
+impl PanicError {
+    /// Creates a new error from the payload of a caught panic, payloads
+    /// of `&str` and `String` are classified from their message
+    pub fn from_payload(payload: &(dyn Any + Send)) -> Self {
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+
+        match message {
+            Some(message) => {
+                let kind = PanicKind::classify(message);
+                Self {
+                    kind,
+                    message: Some(normalize(kind, message)),
+                }
+            }
+            None => Self {
+                kind: PanicKind::Opaque,
+                message: None,
+            },
+        }
+    }
+
+    /// Category of the panic
+    pub fn panic_kind(&self) -> PanicKind {
+        self.kind
+    }
+
+    /// Normalized panic message, [None] when the payload wasn't a string
+    pub fn message(&self) -> Option<&str> {
+        self.message.as_deref()
+    }
+
+    /// Fingerprint of the panic from its category and normalized message,
+    /// finer grained than the error code for grouping panics by where
+    /// they occurred. The fingerprint is a 64-bit FNV-1a hash which is
+    /// stable across Rust releases, platforms and restarts
+    pub fn fingerprint(&self) -> u64 {
+        let mut hasher = StableHasher::new();
+        hasher.write_str(self.kind.code());
+        hasher.write_option_str(self.message.as_deref());
+        hasher.finish()
+    }
+}
+
+impl Display for PanicError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.message {
+            Some(message) => write!(f, "Handler panicked: {message}"),
+            None => f.write_str("Handler panicked"),
+        }
+    }
+}
+
+impl Error for PanicError {}
+
+impl HttpError for PanicError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        log::error!("{self} ({:016x})", self.fingerprint());
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::INTERNAL_SERVER_ERROR
+    }
+
+    fn reason(&self) -> String {
+        "Server error".to_string()
+    }
+
+    fn code(&self) -> Option<&str> {
+        Some(self.kind.code())
+    }
+}
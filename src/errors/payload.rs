@@ -0,0 +1,109 @@
+//! Errors for request payloads that can't be accepted
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::{header::ACCEPT, HeaderMap, HeaderName, HeaderValue, StatusCode};
+use mime::Mime;
+
+use crate::HttpError;
+
+/// Error for request payloads that exceed the maximum allowed
+/// size (413 Payload Too Large)
+#[derive(Debug)]
+pub struct PayloadTooLarge {
+    /// The maximum allowed payload size in bytes
+    pub limit: usize,
+}
+
+impl PayloadTooLarge {
+    /// Creates a new error for the provided limit in bytes
+    pub fn new(limit: usize) -> Self {
+        Self { limit }
+    }
+}
+
+impl Display for PayloadTooLarge {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Payload too large, the maximum size is {} bytes",
+            self.limit
+        )
+    }
+}
+
+impl Error for PayloadTooLarge {}
+
+impl HttpError for PayloadTooLarge {
+    fn status(&self) -> StatusCode {
+        StatusCode::PAYLOAD_TOO_LARGE
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({ "limit": self.limit }))
+    }
+}
+
+/// Error for request payloads using a media type that isn't supported
+/// (415 Unsupported Media Type), the supported media types are listed
+/// in the `Accept` header of the response
+#[derive(Debug)]
+pub struct UnsupportedMediaType {
+    /// The supported media types
+    pub supported: Vec<Mime>,
+    /// The header used to list the supported media types
+    header: HeaderName,
+}
+
+impl UnsupportedMediaType {
+    /// Creates a new error listing the provided supported media types
+    pub fn new(supported: Vec<Mime>) -> Self {
+        Self {
+            supported,
+            header: ACCEPT,
+        }
+    }
+
+    /// Sets the header used to list the supported media types, such as
+    /// `Accept-Post` or `Accept-Patch` (Default: `Accept`)
+    pub fn header(mut self, header: HeaderName) -> Self {
+        self.header = header;
+        self
+    }
+}
+
+impl Display for UnsupportedMediaType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Unsupported media type")
+    }
+}
+
+impl Error for UnsupportedMediaType {}
+
+impl HttpError for UnsupportedMediaType {
+    fn status(&self) -> StatusCode {
+        StatusCode::UNSUPPORTED_MEDIA_TYPE
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        let supported = self
+            .supported
+            .iter()
+            .map(Mime::as_ref)
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        if let Ok(value) = HeaderValue::from_str(&supported) {
+            headers.insert(self.header.clone(), value);
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        let supported = self.supported.iter().map(Mime::as_ref).collect::<Vec<_>>();
+
+        Some(serde_json::json!({ "supported": supported }))
+    }
+}
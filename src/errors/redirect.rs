@@ -0,0 +1,79 @@
+//! Error for redirecting requests through the error channel
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::{header::LOCATION, HeaderMap, HeaderValue, StatusCode};
+
+use crate::HttpError;
+
+/// Error that redirects the client to another location, allowing
+/// fallible handlers to `?` return a redirect (i.e redirecting to the
+/// login page when the session has expired)
+#[derive(Debug, Clone)]
+pub struct RedirectError {
+    /// The redirect status code
+    status: StatusCode,
+    /// The location to redirect to
+    location: String,
+}
+
+impl RedirectError {
+    /// Redirect using 303 See Other, the client will follow the
+    /// redirect using a GET request
+    pub fn see_other(location: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::SEE_OTHER,
+            location: location.into(),
+        }
+    }
+
+    /// Redirect using 307 Temporary Redirect, the client will follow the
+    /// redirect using the same method and body
+    pub fn temporary(location: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::TEMPORARY_REDIRECT,
+            location: location.into(),
+        }
+    }
+
+    /// Redirect using 308 Permanent Redirect, the client will follow the
+    /// redirect using the same method and body
+    pub fn permanent(location: impl Into<String>) -> Self {
+        Self {
+            status: StatusCode::PERMANENT_REDIRECT,
+            location: location.into(),
+        }
+    }
+
+    /// The location being redirected to
+    pub fn location(&self) -> &str {
+        &self.location
+    }
+}
+
+impl Display for RedirectError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Redirecting to {}", self.location)
+    }
+}
+
+impl Error for RedirectError {}
+
+impl HttpError for RedirectError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        // Redirects are expected behavior so they aren't logged as errors
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        if let Ok(value) = HeaderValue::from_str(&self.location) {
+            headers.insert(LOCATION, value);
+        }
+    }
+}
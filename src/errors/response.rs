@@ -0,0 +1,79 @@
+//! Error wrapping an already built response
+
+use std::error::Error;
+use std::fmt::{Debug, Display};
+use std::sync::Mutex;
+
+use axum_core::response::Response;
+use http::StatusCode;
+
+use crate::HttpError;
+
+/// Error wrapping an already built [Response] so that custom responses can
+/// be returned through [HttpResult](crate::HttpResult), the error still goes
+/// through the logging, hooks and metrics but the response is sent as is
+/// (status, headers and body) instead of being created by the formatter
+///
+/// Error types that always create their own response can implement
+/// [HttpError::into_response_override] instead
+///
+/// ```
+/// use axum::response::{IntoResponse, Response};
+/// use axum_dyn_error::{DynHttpError, HttpResult, StatusCode};
+///
+/// pub async fn legacy() -> HttpResult<String> {
+///     let response: Response = (StatusCode::IM_A_TEAPOT, "Custom body").into_response();
+///     Err(DynHttpError::from_response(response))
+/// }
+/// ```
+pub struct ResponseError {
+    /// The status of the response
+    status: StatusCode,
+    /// The response, taken when the error is converted into a response
+    response: Mutex<Option<Response>>,
+}
+
+impl ResponseError {
+    /// Creates an error wrapping the provided response
+    pub fn new(response: Response) -> Self {
+        Self {
+            status: response.status(),
+            response: Mutex::new(Some(response)),
+        }
+    }
+
+    /// Takes the wrapped response, returns [None] if the response
+    /// was already taken
+    pub fn take_response(&self) -> Option<Response> {
+        self.response
+            .lock()
+            .unwrap_or_else(|err| err.into_inner())
+            .take()
+    }
+}
+
+impl Debug for ResponseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ResponseError")
+            .field("status", &self.status)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Display for ResponseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Custom error response with status {}", self.status)
+    }
+}
+
+impl Error for ResponseError {}
+
+impl HttpError for ResponseError {
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn into_response_override(&self) -> Option<Response> {
+        self.take_response()
+    }
+}
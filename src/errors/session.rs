@@ -0,0 +1,215 @@
+//! Errors for rejected sessions that reset the session state of the
+//! client by clearing cookies
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::{header::SET_COOKIE, HeaderMap, HeaderValue, StatusCode};
+
+use crate::HttpError;
+
+/// Cookie that is cleared by an error response, cleared using a
+/// `Set-Cookie` header with an empty value that has already expired
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClearCookie {
+    /// Name of the cookie
+    name: String,
+    /// Path the cookie was set for
+    path: String,
+    /// Domain the cookie was set for
+    domain: Option<String>,
+}
+
+impl ClearCookie {
+    /// Creates a cookie to clear with the provided name for the `/` path
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            path: "/".to_string(),
+            domain: None,
+        }
+    }
+
+    /// Sets the path the cookie was set for, must match the path of the
+    /// cookie for it to be cleared
+    pub fn path(mut self, path: impl Into<String>) -> Self {
+        self.path = path.into();
+        self
+    }
+
+    /// Sets the domain the cookie was set for, must match the domain of
+    /// the cookie for it to be cleared
+    pub fn domain(mut self, domain: impl Into<String>) -> Self {
+        self.domain = Some(domain.into());
+        self
+    }
+
+    /// Name of the cookie
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Creates the `Set-Cookie` header value that clears the cookie
+    pub fn header_value(&self) -> Option<HeaderValue> {
+        let mut value = format!(
+            "{}=; Path={}; Max-Age=0; Expires=Thu, 01 Jan 1970 00:00:00 GMT",
+            self.name, self.path
+        );
+        if let Some(domain) = &self.domain {
+            value.push_str("; Domain=");
+            value.push_str(domain);
+        }
+        HeaderValue::from_str(&value).ok()
+    }
+}
+
+impl From<&str> for ClearCookie {
+    fn from(value: &str) -> Self {
+        Self::new(value)
+    }
+}
+
+impl From<String> for ClearCookie {
+    fn from(value: String) -> Self {
+        Self::new(value)
+    }
+}
+
+/// Appends the `Set-Cookie` headers clearing the cookies
+fn clear_cookies(cookies: &[ClearCookie], headers: &mut HeaderMap) {
+    for value in cookies.iter().filter_map(ClearCookie::header_value) {
+        headers.append(SET_COOKIE, value);
+    }
+}
+
+/// Error for when the session of the client has expired or is no longer
+/// valid (401 Unauthorized), the session cookies are cleared so the client
+/// starts a fresh session
+///
+/// ```
+/// use axum_dyn_error::{ClearCookie, HttpResult, SessionExpired};
+///
+/// pub async fn dashboard() -> HttpResult<String> {
+///     Err(SessionExpired::new()
+///         .clear_cookie("session")
+///         .clear_cookie(ClearCookie::new("remember").path("/auth"))
+///         .into())
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct SessionExpired {
+    /// The reason the session was rejected
+    reason: String,
+    /// The cookies to clear
+    cookies: Vec<ClearCookie>,
+}
+
+impl Default for SessionExpired {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SessionExpired {
+    /// Creates a new error without any cookies to clear
+    pub fn new() -> Self {
+        Self {
+            reason: "Session expired".to_string(),
+            cookies: Vec::new(),
+        }
+    }
+
+    /// Sets the reason the session was rejected
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = reason.into();
+        self
+    }
+
+    /// Adds a cookie to clear in the response
+    pub fn clear_cookie(mut self, cookie: impl Into<ClearCookie>) -> Self {
+        self.cookies.push(cookie.into());
+        self
+    }
+
+    /// The cookies cleared by the response
+    pub fn cookies(&self) -> &[ClearCookie] {
+        &self.cookies
+    }
+}
+
+impl Display for SessionExpired {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.reason)
+    }
+}
+
+impl Error for SessionExpired {}
+
+impl HttpError for SessionExpired {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        // Expired sessions are expected behavior so they aren't logged as errors
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::UNAUTHORIZED
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        clear_cookies(&self.cookies, headers);
+    }
+}
+
+/// Error for when the CSRF token of a request is missing or doesn't match
+/// the token of the session (403 Forbidden), the CSRF cookies are cleared
+/// so that a new token is issued
+///
+/// ```
+/// use axum_dyn_error::{CsrfMismatch, HttpResult};
+///
+/// pub async fn submit() -> HttpResult<()> {
+///     Err(CsrfMismatch::new().clear_cookie("csrf_token").into())
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct CsrfMismatch {
+    /// The cookies to clear
+    cookies: Vec<ClearCookie>,
+}
+
+impl CsrfMismatch {
+    /// Creates a new error without any cookies to clear
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a cookie to clear in the response
+    pub fn clear_cookie(mut self, cookie: impl Into<ClearCookie>) -> Self {
+        self.cookies.push(cookie.into());
+        self
+    }
+
+    /// The cookies cleared by the response
+    pub fn cookies(&self) -> &[ClearCookie] {
+        &self.cookies
+    }
+}
+
+impl Display for CsrfMismatch {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("CSRF token mismatch")
+    }
+}
+
+impl Error for CsrfMismatch {}
+
+impl HttpError for CsrfMismatch {
+    fn status(&self) -> StatusCode {
+        StatusCode::FORBIDDEN
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        clear_cookies(&self.cookies, headers);
+    }
+}
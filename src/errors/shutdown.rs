@@ -0,0 +1,38 @@
+//! Error for requests received while the server is shutting down
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::StatusCode;
+
+use crate::{ConnectionDirective, HttpError};
+
+/// Error for requests received after a graceful shutdown has begun (503
+/// Service Unavailable), the connection is closed after the response so
+/// that clients reconnect to another instance
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ShuttingDown;
+
+impl Display for ShuttingDown {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Server is shutting down")
+    }
+}
+
+impl Error for ShuttingDown {}
+
+impl HttpError for ShuttingDown {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        // Rejected requests are expected while draining
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+
+    fn connection_directive(&self) -> ConnectionDirective {
+        ConnectionDirective::Close
+    }
+}
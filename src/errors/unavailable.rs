@@ -0,0 +1,110 @@
+//! Errors for services that are temporarily unavailable
+
+use std::error::Error;
+use std::fmt::Display;
+use std::time::{Duration, SystemTime};
+
+use http::{header::RETRY_AFTER, HeaderMap, HeaderValue, StatusCode};
+
+use crate::{ConnectionDirective, HttpError};
+
+/// Error for when the service is temporarily unable to handle requests
+/// (503 Service Unavailable) such as during scheduled maintenance.
+///
+/// When the end of the unavailable window is known its included in the
+/// `Retry-After` header and the response details
+#[derive(Debug, Clone)]
+pub struct ServiceUnavailable {
+    /// The reason the service is unavailable
+    reason: String,
+    /// When the service is expected to be available again
+    until: Option<SystemTime>,
+    /// Whether the connection should be closed after the response
+    close_connection: bool,
+}
+
+impl Default for ServiceUnavailable {
+    fn default() -> Self {
+        Self::new("Service unavailable")
+    }
+}
+
+impl ServiceUnavailable {
+    /// Creates a new error with the provided reason
+    pub fn new(reason: impl Into<String>) -> Self {
+        Self {
+            reason: reason.into(),
+            until: None,
+            close_connection: false,
+        }
+    }
+
+    /// Creates a new error for scheduled maintenance ending at the
+    /// provided time
+    pub fn maintenance(until: SystemTime) -> Self {
+        Self::new("Service unavailable for scheduled maintenance").until(until)
+    }
+
+    /// Sets the time the service is expected to be available again
+    pub fn until(mut self, until: SystemTime) -> Self {
+        self.until = Some(until);
+        self
+    }
+
+    /// Sets the duration until the service is expected to be available
+    /// again relative to now
+    pub fn retry_after(self, duration: Duration) -> Self {
+        self.until(SystemTime::now() + duration)
+    }
+
+    /// Sets whether the connection should be closed after the response
+    /// is sent, for draining an unhealthy instance (See [ConnectionDirective])
+    pub fn close_connection(mut self, value: bool) -> Self {
+        self.close_connection = value;
+        self
+    }
+}
+
+impl Display for ServiceUnavailable {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.until {
+            Some(until) => write!(
+                f,
+                "{} until {}",
+                self.reason,
+                httpdate::fmt_http_date(until)
+            ),
+            None => f.write_str(&self.reason),
+        }
+    }
+}
+
+impl Error for ServiceUnavailable {}
+
+impl HttpError for ServiceUnavailable {
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        if let Some(until) = self.until {
+            if let Ok(value) = HeaderValue::from_str(&httpdate::fmt_http_date(until)) {
+                headers.insert(RETRY_AFTER, value);
+            }
+        }
+    }
+
+    fn connection_directive(&self) -> ConnectionDirective {
+        if self.close_connection {
+            ConnectionDirective::Close
+        } else {
+            ConnectionDirective::Keep
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        self.until
+            .map(|until| serde_json::json!({ "until": httpdate::fmt_http_date(until) }))
+    }
+}
@@ -0,0 +1,133 @@
+//! Error for failed requests to upstream services
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::{HeaderMap, HeaderName, StatusCode};
+
+use crate::{ErrorKind, HttpError};
+
+/// Maximum number of bytes of the upstream response body that
+/// are kept by [UpstreamHttpError]
+pub const UPSTREAM_BODY_LIMIT: usize = 1024;
+
+/// Error for gateway services wrapping an unsuccessful response from
+/// an upstream service
+///
+/// By default the response is a 502 Bad Gateway, use [UpstreamHttpError::pass_through]
+/// to respond with the upstream status instead. The upstream status and body are
+/// always logged but only included in the error details when enabled using
+/// [UpstreamHttpError::expose_details]
+#[derive(Debug)]
+pub struct UpstreamHttpError {
+    /// The status of the upstream response
+    pub upstream_status: StatusCode,
+    /// The headers of the upstream response
+    pub upstream_headers: HeaderMap,
+    /// The upstream response body, truncated to [UPSTREAM_BODY_LIMIT] bytes
+    pub upstream_body: String,
+    /// Names of the upstream headers included in the error response
+    forwarded: Vec<HeaderName>,
+    /// Whether to respond with the upstream status
+    pass_through: bool,
+    /// Whether to include the upstream response in the error details
+    expose_details: bool,
+}
+
+impl UpstreamHttpError {
+    /// Creates a new error from the status, headers and body of the
+    /// upstream response
+    pub fn new(status: StatusCode, headers: HeaderMap, body: impl AsRef<[u8]>) -> Self {
+        let body = body.as_ref();
+        let body = &body[..body.len().min(UPSTREAM_BODY_LIMIT)];
+
+        Self {
+            upstream_status: status,
+            upstream_headers: headers,
+            upstream_body: String::from_utf8_lossy(body).into_owned(),
+            forwarded: Vec::new(),
+            pass_through: false,
+            expose_details: false,
+        }
+    }
+
+    /// Includes the upstream header with the provided name in the
+    /// error response (i.e `Retry-After`)
+    pub fn forward_header(mut self, name: HeaderName) -> Self {
+        self.forwarded.push(name);
+        self
+    }
+
+    /// Sets whether the upstream status should be used for the error
+    /// response rather than 502 Bad Gateway
+    pub fn pass_through(mut self, value: bool) -> Self {
+        self.pass_through = value;
+        self
+    }
+
+    /// Sets whether the upstream status and body should be included
+    /// in the error details
+    pub fn expose_details(mut self, value: bool) -> Self {
+        self.expose_details = value;
+        self
+    }
+}
+
+impl Display for UpstreamHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Upstream service responded with {}",
+            self.upstream_status
+        )
+    }
+}
+
+impl Error for UpstreamHttpError {}
+
+impl HttpError for UpstreamHttpError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        log::error!(
+            "{self}: {}{}",
+            self.upstream_body,
+            crate::location::log_suffix()
+        );
+    }
+
+    fn status(&self) -> StatusCode {
+        if self.pass_through {
+            self.upstream_status
+        } else {
+            StatusCode::BAD_GATEWAY
+        }
+    }
+
+    fn reason(&self) -> String {
+        "Upstream service error".to_string()
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        for name in &self.forwarded {
+            for value in self.upstream_headers.get_all(name) {
+                headers.append(name.clone(), value.clone());
+            }
+        }
+    }
+
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::Upstream
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        if !self.expose_details {
+            return None;
+        }
+
+        Some(serde_json::json!({
+            "upstream_status": self.upstream_status.as_u16(),
+            "upstream_body": self.upstream_body,
+        }))
+    }
+}
@@ -0,0 +1,45 @@
+//! Support for the rejections of `axum-extra` extractors
+//!
+//! The rejections implement [HttpError] so they can be converted into
+//! [DynHttpError](crate::DynHttpError) using the `?` operator, handlers
+//! can take the extractor as a `Result` to use the error format of this
+//! crate instead of the default rejection response
+//!
+//! ```
+//! use axum_extra::{headers::UserAgent, typed_header::TypedHeaderRejection, TypedHeader};
+//! use axum_dyn_error::HttpResult;
+//!
+//! pub async fn handler(
+//!     user_agent: Result<TypedHeader<UserAgent>, TypedHeaderRejection>,
+//! ) -> HttpResult<String> {
+//!     let TypedHeader(user_agent) = user_agent?;
+//!     Ok(user_agent.to_string())
+//! }
+//! ```
+
+use axum_extra::typed_header::TypedHeaderRejection;
+use http::StatusCode;
+
+use crate::HttpError;
+
+/// Missing or invalid typed headers are 400 Bad Request errors with
+/// the name of the header included in the details
+impl HttpError for TypedHeaderRejection {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        // Rejections are caused by the client so they aren't logged as errors
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "header": self.name().as_str(),
+            "missing": self.is_missing(),
+        }))
+    }
+}
@@ -0,0 +1,138 @@
+//! gRPC status codes for errors, allowing a single error type to be
+//! shared by HTTP and gRPC services
+
+use std::fmt::Display;
+
+use http::StatusCode;
+
+/// Canonical gRPC status code of an error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "SCREAMING_SNAKE_CASE"))]
+pub enum GrpcCode {
+    /// Not an error
+    Ok = 0,
+    /// The operation was cancelled, typically by the caller
+    Cancelled = 1,
+    /// Unknown error
+    Unknown = 2,
+    /// The client specified an invalid argument
+    InvalidArgument = 3,
+    /// The deadline expired before the operation could complete
+    DeadlineExceeded = 4,
+    /// The requested entity was not found
+    NotFound = 5,
+    /// The entity the client attempted to create already exists
+    AlreadyExists = 6,
+    /// The caller doesn't have permission to execute the operation
+    PermissionDenied = 7,
+    /// Some resource has been exhausted (i.e a rate limit)
+    ResourceExhausted = 8,
+    /// The system is not in a state required for the operation
+    FailedPrecondition = 9,
+    /// The operation was aborted, typically due to a concurrency issue
+    Aborted = 10,
+    /// The operation was attempted past the valid range
+    OutOfRange = 11,
+    /// The operation is not implemented or supported
+    Unimplemented = 12,
+    /// Internal error
+    Internal = 13,
+    /// The service is currently unavailable
+    Unavailable = 14,
+    /// Unrecoverable data loss or corruption
+    DataLoss = 15,
+    /// The request does not have valid authentication credentials
+    Unauthenticated = 16,
+}
+
+impl GrpcCode {
+    /// Determines the gRPC code from the HTTP status code, this is used
+    /// for errors that don't specify their own code
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            status if status.is_success() => Self::Ok,
+            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => Self::InvalidArgument,
+            StatusCode::UNAUTHORIZED => Self::Unauthenticated,
+            StatusCode::FORBIDDEN => Self::PermissionDenied,
+            StatusCode::NOT_FOUND | StatusCode::GONE => Self::NotFound,
+            StatusCode::CONFLICT => Self::Aborted,
+            StatusCode::PRECONDITION_FAILED => Self::FailedPrecondition,
+            StatusCode::RANGE_NOT_SATISFIABLE => Self::OutOfRange,
+            StatusCode::PAYLOAD_TOO_LARGE | StatusCode::TOO_MANY_REQUESTS => {
+                Self::ResourceExhausted
+            }
+            StatusCode::METHOD_NOT_ALLOWED | StatusCode::NOT_IMPLEMENTED => Self::Unimplemented,
+            StatusCode::REQUEST_TIMEOUT | StatusCode::GATEWAY_TIMEOUT => Self::DeadlineExceeded,
+            StatusCode::BAD_GATEWAY | StatusCode::SERVICE_UNAVAILABLE => Self::Unavailable,
+            status if status.as_u16() == 499 => Self::Cancelled,
+            status if status.is_client_error() => Self::FailedPrecondition,
+            status if status.is_server_error() => Self::Internal,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// The numeric value of the code
+    pub fn as_i32(&self) -> i32 {
+        *self as i32
+    }
+
+    /// Stable name of the code as used by gRPC (i.e `NOT_FOUND`)
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Ok => "OK",
+            Self::Cancelled => "CANCELLED",
+            Self::Unknown => "UNKNOWN",
+            Self::InvalidArgument => "INVALID_ARGUMENT",
+            Self::DeadlineExceeded => "DEADLINE_EXCEEDED",
+            Self::NotFound => "NOT_FOUND",
+            Self::AlreadyExists => "ALREADY_EXISTS",
+            Self::PermissionDenied => "PERMISSION_DENIED",
+            Self::ResourceExhausted => "RESOURCE_EXHAUSTED",
+            Self::FailedPrecondition => "FAILED_PRECONDITION",
+            Self::Aborted => "ABORTED",
+            Self::OutOfRange => "OUT_OF_RANGE",
+            Self::Unimplemented => "UNIMPLEMENTED",
+            Self::Internal => "INTERNAL",
+            Self::Unavailable => "UNAVAILABLE",
+            Self::DataLoss => "DATA_LOSS",
+            Self::Unauthenticated => "UNAUTHENTICATED",
+        }
+    }
+}
+
+impl Display for GrpcCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(feature = "tonic")]
+mod tonic_interop {
+    use super::GrpcCode;
+    use crate::{DynHttpError, IntoHttpErrorResponse};
+
+    impl From<GrpcCode> for tonic::Code {
+        fn from(value: GrpcCode) -> Self {
+            tonic::Code::from_i32(value.as_i32())
+        }
+    }
+
+    /// Converts the error into a gRPC status using the [HttpError::grpc_code](crate::HttpError::grpc_code)
+    /// and the reason of the error as the message, the error is logged the same
+    /// as when its converted into a HTTP response
+    impl<I: IntoHttpErrorResponse> From<DynHttpError<I>> for tonic::Status {
+        fn from(value: DynHttpError<I>) -> Self {
+            #[cfg(all(feature = "log", feature = "track-caller"))]
+            let location = value.location;
+            let error = value.inner.into_boxed();
+
+            #[cfg(all(feature = "log", feature = "track-caller"))]
+            crate::location::with_location(location, || error.log());
+            #[cfg(all(feature = "log", not(feature = "track-caller")))]
+            error.log();
+
+            tonic::Status::new(error.grpc_code().into(), error.reason())
+        }
+    }
+}
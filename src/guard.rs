@@ -0,0 +1,51 @@
+//! Macros for authorization checks within handlers
+
+/// Returns early with a 403 Forbidden [DynHttpError](crate::DynHttpError)
+/// unless the condition is true, the message accepts the same arguments
+/// as [format!] (Default: "Forbidden")
+///
+/// ```
+/// use axum_dyn_error::{http_guard, HttpResult};
+///
+/// pub struct User { admin: bool }
+///
+/// fn delete_user(user: &User) -> HttpResult<()> {
+///     http_guard!(user.admin, "Admin required");
+///     Ok(())
+/// }
+/// ```
+#[macro_export]
+macro_rules! http_guard {
+    ($cond:expr $(,)?) => {
+        $crate::http_guard!($cond, "Forbidden")
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if !($cond) {
+            return ::std::result::Result::Err($crate::DynHttpError::forbidden(format!($($arg)+)));
+        }
+    };
+}
+
+/// Returns early with a 401 Unauthorized [DynHttpError](crate::DynHttpError)
+/// unless the condition is true, the message accepts the same arguments
+/// as [format!] (Default: "Unauthorized")
+///
+/// ```
+/// use axum_dyn_error::{http_guard_authenticated, HttpResult};
+///
+/// fn current_user(session: Option<u32>) -> HttpResult<u32> {
+///     http_guard_authenticated!(session.is_some(), "Login required");
+///     Ok(session.unwrap())
+/// }
+/// ```
+#[macro_export]
+macro_rules! http_guard_authenticated {
+    ($cond:expr $(,)?) => {
+        $crate::http_guard_authenticated!($cond, "Unauthorized")
+    };
+    ($cond:expr, $($arg:tt)+) => {
+        if !($cond) {
+            return ::std::result::Result::Err($crate::DynHttpError::unauthorized(format!($($arg)+)));
+        }
+    };
+}
@@ -0,0 +1,85 @@
+//! HTML error responses
+
+use axum_core::response::{IntoResponse, Response};
+use http::{header::CONTENT_TYPE, request::Parts, HeaderValue};
+
+use crate::{HttpError, IntoHttpErrorResponse};
+
+/// Creates HTTP error responses containing a minimal HTML error page
+/// showing the status and reason of the error.
+///
+/// When the `debug-errors` feature is enabled and [DebugErrors](crate::DebugErrors)
+/// has been enabled at runtime the page for server errors (5xx) also
+/// includes the error type, error chain, backtrace and request details
+pub struct HtmlErrorResponse;
+
+impl IntoHttpErrorResponse for HtmlErrorResponse {
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        Self::into_response_with_parts(error, None)
+    }
+
+    #[cfg_attr(not(feature = "debug-errors"), allow(unused_variables))]
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        let status = error.status();
+        let title = format!("{} {}", status.as_u16(), crate::reason_phrase(status));
+
+        let mut body = String::new();
+        body.push_str(&format!("<h1>{}</h1>", escape_html(&title)));
+        body.push_str(&format!("<p>{}</p>", escape_html(&error.reason())));
+
+        #[cfg(feature = "debug-errors")]
+        if status.is_server_error() && crate::DebugErrors::is_enabled() {
+            body.push_str(
+                &crate::DebugInfo::from_error_with_parts(error.as_ref(), parts).to_html(),
+            );
+        }
+
+        html_response(status, &title, &body)
+    }
+}
+
+/// Basic styling for the HTML error pages
+const STYLE: &str = "body{font-family:system-ui,sans-serif;max-width:960px;margin:2rem auto;padding:0 1rem;color:#222}\
+h1{border-bottom:1px solid #ddd;padding-bottom:.5rem}\
+.debug{margin-top:2rem;padding:1rem;background:#fff4f4;border:1px solid #f0b4b4;border-radius:4px}\
+pre{overflow-x:auto;background:#f6f6f6;padding:.75rem;font-size:.8rem}\
+table{border-collapse:collapse;font-size:.85rem}th,td{text-align:left;padding:.2rem .5rem;border-bottom:1px solid #eee}";
+
+/// Creates an HTML response wrapping the provided body contents in a
+/// minimal page with the provided title
+pub(crate) fn html_response(status: http::StatusCode, title: &str, body: &str) -> Response {
+    let page = format!(
+        "<!DOCTYPE html><html><head><meta charset=\"utf-8\"><title>{}</title><style>{}</style></head><body>{}</body></html>",
+        escape_html(title),
+        STYLE,
+        body
+    );
+
+    html_page_response(status, page)
+}
+
+/// Creates an HTML response from a complete page
+pub(crate) fn html_page_response(status: http::StatusCode, page: String) -> Response {
+    let mut response = (status, page).into_response();
+    response.headers_mut().insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static("text/html; charset=utf-8"),
+    );
+    response
+}
+
+/// Escapes the provided text for use within HTML
+pub fn escape_html(text: &str) -> String {
+    let mut output = String::with_capacity(text.len());
+    for char in text.chars() {
+        match char {
+            '&' => output.push_str("&amp;"),
+            '<' => output.push_str("&lt;"),
+            '>' => output.push_str("&gt;"),
+            '"' => output.push_str("&quot;"),
+            '\'' => output.push_str("&#39;"),
+            char => output.push(char),
+        }
+    }
+    output
+}
@@ -0,0 +1,109 @@
+//! Formatter for htmx applications rendering HTML fragments for requests
+//! made by htmx and full responses for other requests
+
+use std::marker::PhantomData;
+
+use axum_core::response::Response;
+use http::{request::Parts, HeaderName, HeaderValue, StatusCode};
+
+use crate::html::{escape_html, html_page_response};
+use crate::{HttpError, IntoHttpErrorResponse, Negotiated};
+
+/// Header set by htmx on the requests it makes
+pub const HX_REQUEST: HeaderName = HeaderName::from_static("hx-request");
+
+/// Header for changing the element htmx swaps the response into
+pub const HX_RETARGET: HeaderName = HeaderName::from_static("hx-retarget");
+
+/// Header for changing how htmx swaps the response
+pub const HX_RESWAP: HeaderName = HeaderName::from_static("hx-reswap");
+
+/// Rules used by the [HtmxErrorResponse] formatter
+pub trait HtmxRules {
+    /// CSS selector of the element the error fragment is swapped into
+    const TARGET: &'static str = "#errors";
+
+    /// How the error fragment is swapped into the target (i.e `innerHTML`
+    /// or `beforeend`)
+    const SWAP: &'static str = "innerHTML";
+
+    /// Whether the fragment is sent with a 200 OK status, by default htmx
+    /// doesn't swap error responses unless configured to do so using
+    /// `htmx.config.responseHandling`
+    const SWAP_AS_SUCCESS: bool = false;
+
+    /// Creates the HTML fragment for the error
+    fn fragment(error: &dyn HttpError) -> String {
+        format!(
+            "<div class=\"error\" role=\"alert\">{}</div>",
+            escape_html(&error.reason())
+        )
+    }
+}
+
+/// [HtmxRules] swapping a minimal fragment into the `#errors` element
+pub struct DefaultHtmx;
+
+impl HtmxRules for DefaultHtmx {}
+
+/// Formatter that responds to requests made by htmx (requests with the
+/// `HX-Request` header) with an HTML fragment and the `HX-Retarget` and
+/// `HX-Reswap` headers from the rules `R`, other requests use the
+/// fallback formatter `F` which by default negotiates between a full HTML
+/// page and JSON
+///
+/// Requires the request parts to be captured by the `RequestContextLayer`,
+/// without the request parts the fallback formatter is always used
+///
+/// ```
+/// use axum_dyn_error::{HtmxErrorResponse, HtmxRules, HttpResult};
+///
+/// pub struct FormErrors;
+///
+/// impl HtmxRules for FormErrors {
+///     const TARGET: &'static str = "#form-errors";
+///     const SWAP_AS_SUCCESS: bool = true;
+/// }
+///
+/// pub type MyHttpResult<T> = HttpResult<T, HtmxErrorResponse<FormErrors>>;
+/// ```
+pub struct HtmxErrorResponse<R = DefaultHtmx, F = Negotiated> {
+    /// Marker for storing the rules and fallback formatter types
+    _marker: PhantomData<fn() -> (R, F)>,
+}
+
+impl<R, F> IntoHttpErrorResponse for HtmxErrorResponse<R, F>
+where
+    R: HtmxRules,
+    F: IntoHttpErrorResponse,
+{
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        F::into_response(error)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        let is_htmx = parts
+            .and_then(|parts| parts.headers.get(HX_REQUEST))
+            .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"true"));
+
+        if !is_htmx {
+            return F::into_response_with_parts(error, parts);
+        }
+
+        let status = if R::SWAP_AS_SUCCESS {
+            StatusCode::OK
+        } else {
+            error.status()
+        };
+
+        let mut response = html_page_response(status, R::fragment(error.as_ref()));
+        let headers = response.headers_mut();
+        if let Ok(target) = HeaderValue::from_str(R::TARGET) {
+            headers.insert(HX_RETARGET, target);
+        }
+        if let Ok(swap) = HeaderValue::from_str(R::SWAP) {
+            headers.insert(HX_RESWAP, swap);
+        }
+        response
+    }
+}
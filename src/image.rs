@@ -0,0 +1,153 @@
+//! Support for the errors of media processing (`image`)
+//!
+//! Unsupported formats are 415 Unsupported Media Type errors, images that
+//! fail to decode are 422 Unprocessable Entity errors and images exceeding
+//! the decoding limits are 413 Payload Too Large errors. The format of the
+//! image is included in the details
+//!
+//! Upload endpoints accepting specific formats can check the format of
+//! the upload using [UnexpectedImageFormat::check]
+
+use std::error::Error;
+use std::fmt::Display;
+
+use http::StatusCode;
+use image::error::{LimitErrorKind, UnsupportedErrorKind};
+use image::{ImageError, ImageFormat};
+
+use crate::HttpError;
+
+/// Describes the format from a format hint, formats that are known
+/// exactly are described by their MIME type
+#[cfg(feature = "json")]
+fn describe_hint(hint: &image::error::ImageFormatHint) -> Option<String> {
+    use image::error::ImageFormatHint;
+
+    match hint {
+        ImageFormatHint::Exact(format) => Some(format.to_mime_type().to_string()),
+        ImageFormatHint::Unknown => None,
+        hint => Some(hint.to_string()),
+    }
+}
+
+/// Decoding failures keep their meaning for the client, failures encoding
+/// images or caused by the parameters used are server errors with a
+/// generic reason
+impl HttpError for ImageError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_client_error() {
+            log::debug!("{self}");
+        } else {
+            log::error!("{self}{}", crate::location::log_suffix());
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            ImageError::Unsupported(error) => match error.kind() {
+                UnsupportedErrorKind::Format(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+                _ => StatusCode::UNPROCESSABLE_ENTITY,
+            },
+            ImageError::Decoding(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            ImageError::Limits(error) => match error.kind() {
+                LimitErrorKind::DimensionError | LimitErrorKind::InsufficientMemory => {
+                    StatusCode::PAYLOAD_TOO_LARGE
+                }
+                _ => StatusCode::UNPROCESSABLE_ENTITY,
+            },
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn reason(&self) -> String {
+        match self.status() {
+            StatusCode::UNSUPPORTED_MEDIA_TYPE => "Unsupported image format",
+            StatusCode::UNPROCESSABLE_ENTITY => "Invalid image",
+            StatusCode::PAYLOAD_TOO_LARGE => "Image too large",
+            _ => "Server error",
+        }
+        .to_string()
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        let hint = match self {
+            ImageError::Unsupported(error) => error.format_hint(),
+            ImageError::Decoding(error) => error.format_hint(),
+            _ => return None,
+        };
+
+        Some(serde_json::json!({ "detected": describe_hint(&hint) }))
+    }
+}
+
+/// Error for uploads that aren't one of the expected image formats
+/// (415 Unsupported Media Type), the detected and expected formats are
+/// included in the details as MIME types
+///
+/// ```
+/// use axum_dyn_error::{HttpResult, UnexpectedImageFormat};
+/// use image::ImageFormat;
+///
+/// pub async fn upload_avatar(body: Vec<u8>) -> HttpResult<String> {
+///     let format = UnexpectedImageFormat::check(&body, &[ImageFormat::Png, ImageFormat::Jpeg])?;
+///     Ok(format!("Uploaded {}", format.to_mime_type()))
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct UnexpectedImageFormat {
+    /// The format of the image, [None] when the format couldn't be detected
+    pub detected: Option<ImageFormat>,
+    /// The formats that are accepted
+    pub expected: Vec<ImageFormat>,
+}
+
+impl UnexpectedImageFormat {
+    /// Detects the format of the image from its contents, returning the
+    /// format when its one of the expected formats
+    pub fn check(buffer: &[u8], expected: &[ImageFormat]) -> Result<ImageFormat, Self> {
+        let detected = image::guess_format(buffer).ok();
+        match detected {
+            Some(format) if expected.contains(&format) => Ok(format),
+            detected => Err(Self {
+                detected,
+                expected: expected.to_vec(),
+            }),
+        }
+    }
+}
+
+impl Display for UnexpectedImageFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.detected {
+            Some(format) => write!(f, "Unexpected image format {}", format.to_mime_type()),
+            None => f.write_str("Unknown image format"),
+        }
+    }
+}
+
+impl Error for UnexpectedImageFormat {}
+
+impl HttpError for UnexpectedImageFormat {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::UNSUPPORTED_MEDIA_TYPE
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        Some(serde_json::json!({
+            "detected": self.detected.map(|format| format.to_mime_type()),
+            "expected": self
+                .expected
+                .iter()
+                .map(|format| format.to_mime_type())
+                .collect::<Vec<_>>(),
+        }))
+    }
+}
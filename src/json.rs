@@ -0,0 +1,204 @@
+//! JSON error responses
+
+#[cfg(feature = "axum")]
+use axum_core::response::{IntoResponse, Response};
+#[cfg(feature = "axum")]
+use http::{header::CONTENT_TYPE, HeaderValue};
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{ErrorKind, HttpError, IntoHttpErrorResponse};
+
+/// Layouts of the body of the error responses created by [JsonErrorResponse],
+/// new versions are added so that the body can evolve without breaking
+/// existing clients
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ErrorSchemaVersion {
+    /// The [ErrorBody] fields at the top level of the body
+    V1,
+    /// The [ErrorBody] nested within an `error` field alongside the
+    /// `schema_version`
+    V2,
+}
+
+impl ErrorSchemaVersion {
+    /// Obtains the schema version from its number
+    pub const fn from_u32(version: u32) -> Option<Self> {
+        match version {
+            1 => Some(ErrorSchemaVersion::V1),
+            2 => Some(ErrorSchemaVersion::V2),
+            _ => None,
+        }
+    }
+
+    /// The number of the schema version
+    pub const fn as_u32(&self) -> u32 {
+        match self {
+            ErrorSchemaVersion::V1 => 1,
+            ErrorSchemaVersion::V2 => 2,
+        }
+    }
+}
+
+/// Body of the error responses created by [JsonErrorResponse]
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    /// Version of the body schema, [None] when the body is nested within
+    /// a body that specifies the version
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub schema_version: Option<u32>,
+    /// The HTTP status code
+    pub status: u16,
+    /// The machine readable error code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// The broad category of the error
+    pub kind: ErrorKind,
+    /// The reason for the error
+    pub reason: String,
+    /// Advice on how to resolve the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    /// Steps for remediating the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    /// Additional structured details about the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+    /// Whether the details array was truncated
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub truncated: bool,
+    /// Total number of entries in the details array before it
+    /// was truncated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details_count: Option<usize>,
+}
+
+impl ErrorBody {
+    /// Creates the body for the provided error
+    pub fn from_error(error: &dyn HttpError) -> Self {
+        #[cfg_attr(not(feature = "secrecy"), allow(unused_mut))]
+        let mut details = error.details();
+
+        // Redact any sensitive fields that made it into the details
+        #[cfg(feature = "secrecy")]
+        if let Some(details) = &mut details {
+            crate::secret::redact_details(details);
+        }
+
+        Self {
+            schema_version: Some(ErrorSchemaVersion::V1.as_u32()),
+            status: error.status().as_u16(),
+            code: error.code().map(str::to_string),
+            kind: error.kind(),
+            reason: error.reason(),
+            help: error.help(),
+            remediation: error.remediation(),
+            details,
+            truncated: false,
+            details_count: None,
+        }
+    }
+
+    /// Truncates the details array to contain at most `max` entries,
+    /// marking the body as truncated and including the total count
+    /// when entries are removed
+    pub fn truncate_details(&mut self, max: usize) {
+        if let Some(Value::Array(details)) = &mut self.details {
+            if details.len() > max {
+                self.details_count = Some(details.len());
+                self.truncated = true;
+                details.truncate(max);
+            }
+        }
+    }
+}
+
+/// Body of the error responses using [ErrorSchemaVersion::V2]
+#[derive(Serialize)]
+struct ErrorEnvelope {
+    /// Version of the body schema
+    schema_version: u32,
+    /// The error body
+    error: ErrorBody,
+}
+
+/// Creates HTTP error responses with a JSON [ErrorBody] as the
+/// response contents
+///
+/// When the details of an error are an array with more than `MAX_DETAILS`
+/// entries the array is truncated (See [ErrorBody::truncate_details]):
+///
+/// ```
+/// use axum_dyn_error::{HttpResult, JsonErrorResponse};
+///
+/// pub type MyHttpResult<T> = HttpResult<T, JsonErrorResponse<100>>;
+/// ```
+///
+/// The layout of the body is selected using `SCHEMA_VERSION` (See [ErrorSchemaVersion])
+///
+/// ```
+/// use axum_dyn_error::{HttpResult, JsonErrorResponse};
+///
+/// pub type MyHttpResult<T> = HttpResult<T, JsonErrorResponse<{ usize::MAX }, 2>>;
+/// ```
+pub struct JsonErrorResponse<
+    const MAX_DETAILS: usize = { usize::MAX },
+    const SCHEMA_VERSION: u32 = 1,
+>;
+
+impl<const MAX_DETAILS: usize, const SCHEMA_VERSION: u32> IntoHttpErrorResponse
+    for JsonErrorResponse<MAX_DETAILS, SCHEMA_VERSION>
+{
+    #[cfg(feature = "axum")]
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        let version = const {
+            match ErrorSchemaVersion::from_u32(SCHEMA_VERSION) {
+                Some(version) => version,
+                None => panic!("Unsupported error schema version"),
+            }
+        };
+
+        let mut body = ErrorBody::from_error(error.as_ref());
+        body.truncate_details(MAX_DETAILS);
+
+        match version {
+            ErrorSchemaVersion::V1 => json_response(error.status(), &body),
+            ErrorSchemaVersion::V2 => {
+                body.schema_version = None;
+                let envelope = ErrorEnvelope {
+                    schema_version: version.as_u32(),
+                    error: body,
+                };
+                json_response(error.status(), &envelope)
+            }
+        }
+    }
+}
+
+/// Creates a JSON response from the provided status and value
+#[cfg(feature = "axum")]
+pub(crate) fn json_response<T: Serialize>(status: http::StatusCode, value: &T) -> Response {
+    json_response_with_type(status, value, "application/json")
+}
+
+/// Creates a JSON response from the provided status and value using
+/// the provided JSON based content type
+#[cfg(feature = "axum")]
+pub(crate) fn json_response_with_type<T: Serialize>(
+    status: http::StatusCode,
+    value: &T,
+    content_type: &'static str,
+) -> Response {
+    match serde_json::to_vec(value) {
+        Ok(body) => {
+            let mut response = (status, body).into_response();
+            response
+                .headers_mut()
+                .insert(CONTENT_TYPE, HeaderValue::from_static(content_type));
+            response
+        }
+        Err(_) => http::StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
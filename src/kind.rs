@@ -0,0 +1,185 @@
+//! Broad categories of errors for grouping errors in logs and dashboards
+
+use std::fmt::Display;
+
+use http::StatusCode;
+
+use crate::HttpError;
+
+/// Broad category of an error, more descriptive than a status code alone
+/// (i.e distinguishing failures of upstream services from bugs which
+/// would both be reported as 5xx errors)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[non_exhaustive]
+pub enum ErrorKind {
+    /// The request was malformed or failed validation
+    Validation,
+    /// The requested resource doesn't exist
+    NotFound,
+    /// The request conflicts with the current state of a resource
+    Conflict,
+    /// The client is not authenticated or lacks permission
+    Auth,
+    /// The client has exceeded a rate limit
+    RateLimit,
+    /// The service is temporarily unavailable
+    Unavailable,
+    /// A service this service depends on failed or timed out
+    Upstream,
+    /// Unexpected internal error (i.e a bug)
+    Internal,
+    /// Any other error
+    Other,
+}
+
+impl ErrorKind {
+    /// Determines the kind of error from the status code, this is used
+    /// for errors that don't specify their own kind
+    pub fn from_status(status: StatusCode) -> Self {
+        match status {
+            StatusCode::BAD_REQUEST
+            | StatusCode::UNPROCESSABLE_ENTITY
+            | StatusCode::PAYLOAD_TOO_LARGE
+            | StatusCode::UNSUPPORTED_MEDIA_TYPE
+            | StatusCode::URI_TOO_LONG
+            | StatusCode::REQUEST_HEADER_FIELDS_TOO_LARGE => Self::Validation,
+            StatusCode::NOT_FOUND | StatusCode::GONE => Self::NotFound,
+            StatusCode::CONFLICT | StatusCode::PRECONDITION_FAILED => Self::Conflict,
+            StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN => Self::Auth,
+            StatusCode::TOO_MANY_REQUESTS => Self::RateLimit,
+            StatusCode::SERVICE_UNAVAILABLE => Self::Unavailable,
+            StatusCode::BAD_GATEWAY | StatusCode::GATEWAY_TIMEOUT => Self::Upstream,
+            StatusCode::INTERNAL_SERVER_ERROR => Self::Internal,
+            _ => Self::Other,
+        }
+    }
+
+    /// Stable snake case name for the kind, suitable for use as a
+    /// metrics label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Validation => "validation",
+            Self::NotFound => "not_found",
+            Self::Conflict => "conflict",
+            Self::Auth => "auth",
+            Self::RateLimit => "rate_limit",
+            Self::Unavailable => "unavailable",
+            Self::Upstream => "upstream",
+            Self::Internal => "internal",
+            Self::Other => "other",
+        }
+    }
+}
+
+impl Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Classification of an error response, added to the extensions of every
+/// response created from a [DynHttpError](crate::DynHttpError) so that
+/// middleware can inspect the error that produced the response
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorClassification {
+    /// The kind of the error
+    pub kind: ErrorKind,
+    /// Whether the error is transient (See [HttpError::is_transient](crate::HttpError::is_transient))
+    pub transient: bool,
+}
+
+/// Identity of the error that produced a response, added to the extensions
+/// of every response created from a [DynHttpError](crate::DynHttpError)
+/// alongside the [ErrorClassification]. Captured before any rewrites from
+/// the configuration or policies are applied
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ErrorIdentity {
+    /// The full type name of the error
+    pub type_name: String,
+    /// The machine readable error code if the error has one
+    pub code: Option<String>,
+    /// Fingerprint of the error from its type, status and code, stable
+    /// across releases and restarts (See [ErrorEvent::fingerprint](crate::ErrorEvent::fingerprint))
+    pub fingerprint: u64,
+}
+
+impl ErrorIdentity {
+    /// Creates the identity of the provided error
+    pub fn from_error(error: &dyn HttpError) -> Self {
+        Self {
+            type_name: error.type_name().to_string(),
+            code: error.code().map(str::to_string),
+            fingerprint: fingerprint(error),
+        }
+    }
+}
+
+/// Creates a fingerprint for the error from its type, status and code
+pub(crate) fn fingerprint(error: &dyn HttpError) -> u64 {
+    fingerprint_of(error.type_name(), error.status(), error.code())
+}
+
+/// Creates a fingerprint from the type name, status and code of an error
+pub(crate) fn fingerprint_of(type_name: &str, status: StatusCode, code: Option<&str>) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_str(type_name);
+    hasher.write_u16(status.as_u16());
+    hasher.write_option_str(code);
+    hasher.finish()
+}
+
+/// 64-bit FNV-1a hasher for fingerprints. Unlike the standard library
+/// `DefaultHasher` the algorithm and the encoding of each value are fixed,
+/// so fingerprints are stable across Rust releases, platforms and restarts
+/// and can be stored or compared between instances
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    /// FNV-1a 64-bit offset basis
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    /// FNV-1a 64-bit prime
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    /// Creates a new hasher
+    pub(crate) const fn new() -> Self {
+        Self(Self::OFFSET_BASIS)
+    }
+
+    /// Hashes the provided bytes
+    pub(crate) fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u64::from(*byte);
+            self.0 = self.0.wrapping_mul(Self::PRIME);
+        }
+    }
+
+    /// Hashes a string followed by a terminator so that adjacent strings
+    /// can't collide by moving bytes between them
+    pub(crate) fn write_str(&mut self, value: &str) {
+        self.write_bytes(value.as_bytes());
+        self.write_bytes(&[0xff]);
+    }
+
+    /// Hashes an optional string, [None] is distinct from an empty string
+    pub(crate) fn write_option_str(&mut self, value: Option<&str>) {
+        match value {
+            Some(value) => {
+                self.write_bytes(&[1]);
+                self.write_str(value);
+            }
+            None => self.write_bytes(&[0]),
+        }
+    }
+
+    /// Hashes a u16 as little endian bytes
+    pub(crate) fn write_u16(&mut self, value: u16) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    /// The hash of the written values
+    pub(crate) const fn finish(&self) -> u64 {
+        self.0
+    }
+}
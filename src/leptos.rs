@@ -0,0 +1,80 @@
+//! Support for the errors of Leptos server functions (`server_fn`)
+//!
+//! Server function errors can be converted into [DynHttpError] using the
+//! `?` operator so that axum handlers sharing code with server functions
+//! respond using the error format of this crate
+
+use http::StatusCode;
+use server_fn::error::ServerFnErrorErr;
+use server_fn::ServerFnError;
+
+use crate::{DynHttpError, HttpError, IntoHttpError, IntoHttpErrorResponse};
+
+/// Errors caused by the arguments sent by the client are 400 Bad Request
+/// errors, failures reaching another server are 502 Bad Gateway errors and
+/// other errors are server errors with a generic reason
+impl HttpError for ServerFnErrorErr {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_client_error() {
+            log::debug!("{self}");
+        } else {
+            log::error!("{self}{}", crate::location::log_suffix());
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            ServerFnErrorErr::Args(_) | ServerFnErrorErr::MissingArg(_) => StatusCode::BAD_REQUEST,
+            ServerFnErrorErr::Request(_) => StatusCode::BAD_GATEWAY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn reason(&self) -> String {
+        if self.status().is_client_error() {
+            self.to_string()
+        } else {
+            "Server error".to_string()
+        }
+    }
+}
+
+/// Converts a [ServerFnError] into the equivalent [ServerFnErrorErr], the
+/// message of wrapped custom errors is kept as a server error
+#[allow(deprecated)]
+fn into_server_fn_error_err<E: std::fmt::Display>(error: ServerFnError<E>) -> ServerFnErrorErr {
+    match error {
+        ServerFnError::WrappedServerError(error) => {
+            ServerFnErrorErr::ServerError(error.to_string())
+        }
+        ServerFnError::Registration(message) => ServerFnErrorErr::Registration(message),
+        ServerFnError::Request(message) => ServerFnErrorErr::Request(message),
+        ServerFnError::Response(message) => ServerFnErrorErr::Response(message),
+        ServerFnError::ServerError(message) => ServerFnErrorErr::ServerError(message),
+        ServerFnError::MiddlewareError(message) => ServerFnErrorErr::MiddlewareError(message),
+        ServerFnError::Deserialization(message) => ServerFnErrorErr::Deserialization(message),
+        ServerFnError::Serialization(message) => ServerFnErrorErr::Serialization(message),
+        ServerFnError::Args(message) => ServerFnErrorErr::Args(message),
+        ServerFnError::MissingArg(message) => ServerFnErrorErr::MissingArg(message),
+    }
+}
+
+/// Allow conversion from [ServerFnError]s into [DynHttpError] using the
+/// status of the equivalent [ServerFnErrorErr]
+impl<I, E> From<ServerFnError<E>> for DynHttpError<I>
+where
+    I: IntoHttpErrorResponse,
+    E: std::fmt::Display,
+{
+    #[track_caller]
+    fn from(value: ServerFnError<E>) -> Self {
+        into_server_fn_error_err(value).into()
+    }
+}
+
+impl<E: std::fmt::Display> IntoHttpError for ServerFnError<E> {
+    fn into_http_error(self) -> Box<dyn HttpError> {
+        Box::new(into_server_fn_error_err(self))
+    }
+}
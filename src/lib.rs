@@ -1,11 +1,24 @@
 #![warn(missing_docs)]
+#![cfg_attr(feature = "provide", feature(error_generic_member_access))]
 #![doc = include_str!("../README.md")]
 
+use std::convert::Infallible;
 use std::error::Error;
 use std::fmt::{Debug, Display};
 use std::marker::PhantomData;
+#[cfg(feature = "track-caller")]
+use std::panic::Location;
 
+#[cfg(feature = "axum")]
 use axum_core::response::{IntoResponse, Response};
+#[cfg(feature = "axum")]
+use http::header::{CACHE_CONTROL, CONNECTION, CONTENT_LENGTH, CONTENT_TYPE};
+#[cfg(feature = "axum")]
+use http::request::Parts;
+#[cfg(feature = "axum")]
+use http::HeaderValue;
+use http::{Extensions, HeaderMap};
+use repr::ErrorRepr;
 
 // Re-export of status code for ease of use
 pub use http::StatusCode;
@@ -16,10 +29,331 @@ pub mod anyhow;
 #[cfg(feature = "anyhow")]
 pub use anyhow::*;
 
+pub mod audit;
+
+pub use audit::*;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub mod batch;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub use batch::*;
+
+pub mod catalog;
+
+pub use catalog::*;
+
+pub mod chain;
+
+pub use chain::*;
+
+#[cfg(feature = "http-compat")]
+mod compat;
+
+#[cfg(feature = "axum")]
+pub mod compose;
+
+#[cfg(feature = "axum")]
+pub use compose::*;
+
+pub mod config;
+
+pub use config::*;
+
+pub mod connection;
+
+pub use connection::ConnectionDirective;
+
+#[cfg(feature = "debug-errors")]
+pub mod debug;
+
+#[cfg(feature = "debug-errors")]
+pub use debug::*;
+
+#[cfg(all(feature = "log", feature = "axum"))]
+mod dedup;
+
+pub mod convert;
+
+pub use convert::*;
+
+pub mod describe;
+
+pub use describe::*;
+
+pub mod errors;
+
+#[cfg(feature = "log")]
+mod location;
+
+pub mod mapper;
+
+pub use mapper::*;
+
+pub use errors::*;
+
+mod guard;
+
+#[cfg(feature = "axum")]
+pub mod html;
+
+#[cfg(feature = "axum")]
+pub use html::HtmlErrorResponse;
+
+pub mod kind;
+
+pub use kind::{ErrorClassification, ErrorIdentity, ErrorKind};
+
+pub mod grpc;
+
+pub use grpc::GrpcCode;
+
+#[cfg(feature = "json")]
+pub mod json;
+
+#[cfg(feature = "json")]
+pub use json::*;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub mod negotiate;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub use negotiate::*;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub mod htmx;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub use htmx::{DefaultHtmx, HtmxErrorResponse, HtmxRules};
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub mod turbo;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub use turbo::{DefaultTurboStream, TurboStreamErrorResponse, TurboStreamRules};
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub mod problem;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub use problem::*;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub mod vendor;
+
+#[cfg(all(feature = "json", feature = "axum"))]
+pub use vendor::*;
+
+#[cfg(feature = "tower")]
+pub mod context;
+
+#[cfg(feature = "tower")]
+pub use context::*;
+
+#[cfg(feature = "tower")]
+pub mod maintenance;
+
+#[cfg(feature = "tower")]
+pub use maintenance::*;
+
+#[cfg(feature = "tower")]
+pub mod shutdown;
+
+#[cfg(feature = "tower")]
+pub use shutdown::*;
+
+#[cfg(feature = "tower")]
+pub mod startup;
+
+#[cfg(feature = "tower")]
+pub use startup::*;
+
+#[cfg(feature = "tower")]
+pub mod panic;
+
+#[cfg(feature = "tower")]
+pub use panic::{CatchPanicFuture, CatchPanicLayer, CatchPanicService};
+
+#[cfg(all(feature = "tower", feature = "log"))]
+pub mod trace;
+
+#[cfg(all(feature = "tower", feature = "log"))]
+pub use trace::{DynErrorTraceFuture, DynErrorTraceLayer, DynErrorTraceService};
+
+#[cfg(feature = "tower")]
+pub mod service;
+
+#[cfg(feature = "tower")]
+pub use service::{DynErrorFuture, DynErrorLayer, DynErrorService};
+
+#[cfg(feature = "tower")]
+pub mod reporter;
+
+#[cfg(feature = "tower")]
+pub use reporter::*;
+
+#[cfg(feature = "tower")]
+pub mod middleware;
+
+#[cfg(feature = "tower")]
+pub use middleware::{from_fn_dyn_error, FromFnDynErrorLayer, FromFnDynErrorService};
+
+#[cfg(feature = "tower")]
+pub mod policy;
+
+#[cfg(feature = "tower")]
+pub use policy::*;
+
+#[cfg(feature = "tower")]
+pub mod transient;
+
+#[cfg(feature = "tower")]
+pub use transient::*;
+
+#[cfg(feature = "axum")]
+pub mod report;
+
+#[cfg(feature = "axum")]
+pub use report::*;
+
+pub mod sink;
+
+pub use sink::*;
+
+#[cfg(feature = "tower")]
+pub mod summary;
+
+#[cfg(feature = "tower")]
+pub use summary::*;
+
+#[cfg(feature = "webhook")]
+pub mod webhook;
+
+#[cfg(feature = "webhook")]
+pub use webhook::*;
+
+pub mod result;
+
+pub use result::*;
+
+#[cfg(feature = "metrics")]
+pub mod metrics;
+
+#[cfg(feature = "prometheus")]
+pub mod prometheus;
+
+mod repr;
+
+#[cfg(feature = "secrecy")]
+pub mod secret;
+
+#[cfg(feature = "snafu")]
+pub mod snafu;
+
+#[cfg(feature = "snafu")]
+pub use snafu::*;
+
+pub mod status;
+
+pub use status::*;
+
+#[cfg(feature = "axum-extra")]
+pub mod extra;
+
+#[cfg(feature = "leptos")]
+pub mod leptos;
+
+#[cfg(feature = "render")]
+pub mod render;
+
+#[cfg(feature = "stream")]
+pub mod stream;
+
+#[cfg(feature = "render")]
+pub use render::*;
+
+#[cfg(feature = "stream")]
+pub use stream::*;
+
+#[cfg(feature = "tower-http")]
+pub mod classify;
+
+#[cfg(feature = "tower-http")]
+pub use classify::*;
+
+#[cfg(feature = "tower-sessions")]
+pub mod sessions;
+
+#[cfg(feature = "axum-login")]
+pub mod login;
+
+#[cfg(feature = "axum-login")]
+pub use login::AuthSessionExt;
+
+#[cfg(feature = "object-store")]
+pub mod object_store;
+
+#[cfg(any(feature = "lapin", feature = "async-nats"))]
+pub mod messaging;
+
+#[cfg(any(feature = "chrono", feature = "time", feature = "uuid"))]
+pub mod parse;
+
+#[cfg(feature = "csv")]
+pub mod csv;
+
+#[cfg(feature = "image")]
+pub mod image;
+
+#[cfg(feature = "image")]
+pub use image::UnexpectedImageFormat;
+
+#[cfg(feature = "zip")]
+pub mod archive;
+
+#[cfg(feature = "zip")]
+pub use archive::{ArchiveEntryError, UnsafeArchiveEntry};
+
+#[cfg(any(feature = "elasticsearch", feature = "meilisearch"))]
+pub mod search;
+
+#[cfg(feature = "stripe")]
+pub mod stripe;
+
+#[cfg(feature = "secrecy")]
+pub use secret::*;
+
+#[cfg(any(feature = "askama", feature = "maud", feature = "minijinja"))]
+pub mod templates;
+
+#[cfg(any(feature = "askama", feature = "maud", feature = "minijinja"))]
+pub use templates::*;
+mod rewrite;
+
 /// Wrapper around an error type that displays a generic
 /// error message and server status
+///
+/// The [ErrorMapper] is consulted when creating the error which allows
+/// foreign error types to be given a more specific status, otherwise the
+/// default status is used (See [ErrorConfig::default_status])
 pub struct GenericServerError {
-    inner: Box<(dyn Error + Send + Sync + 'static)>,
+    inner: Box<dyn Error + Send + Sync + 'static>,
+    /// Mapping found for the error by the [ErrorMapper]
+    mapping: Option<ErrorMapping>,
+    /// Status used when there isn't a mapping for the error
+    default_status: StatusCode,
+}
+
+impl GenericServerError {
+    /// Creates a generic error from an already boxed error
+    pub fn from_boxed(inner: Box<dyn Error + Send + Sync + 'static>) -> Self {
+        let mapping = ErrorMapper::map(inner.as_ref());
+        Self {
+            inner,
+            mapping,
+            default_status: config::default_status(),
+        }
+    }
 }
 
 /// Extension trait for the [Error] trait for extra functionality
@@ -33,9 +367,7 @@ where
     E: Error + Send + Sync + 'static,
 {
     fn into_generic(self) -> GenericServerError {
-        GenericServerError {
-            inner: Box::new(self),
-        }
+        GenericServerError::from_boxed(Box::new(self))
     }
 }
 
@@ -51,15 +383,34 @@ impl Display for GenericServerError {
     }
 }
 
-impl Error for GenericServerError {}
+impl Error for GenericServerError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.inner.source()
+    }
+
+    #[cfg(feature = "provide")]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        self.inner.provide(request)
+    }
+}
 
 impl HttpError for GenericServerError {
     fn status(&self) -> StatusCode {
-        StatusCode::INTERNAL_SERVER_ERROR
+        match &self.mapping {
+            Some(mapping) => mapping.status,
+            None => self.default_status,
+        }
     }
 
     fn reason(&self) -> String {
-        "Server error".to_string()
+        match self
+            .mapping
+            .as_ref()
+            .and_then(|mapping| mapping.reason.as_ref())
+        {
+            Some(reason) => reason.clone(),
+            None => "Server error".to_string(),
+        }
     }
 }
 
@@ -70,47 +421,546 @@ pub type HttpResult<T, I = TextErrorResponse> = Result<T, DynHttpError<I>>;
 /// home page for usage
 pub struct DynHttpError<I: IntoHttpErrorResponse = TextErrorResponse> {
     /// The dynamically typed http error that created this error
-    inner: Box<dyn HttpError>,
+    inner: ErrorRepr,
     /// Marker for storing the [IntoHttpErrorResponse] type
-    _marker: PhantomData<I>,
+    _marker: PhantomData<fn() -> I>,
+    /// Location where the error was converted into a [DynHttpError]
+    #[cfg(feature = "track-caller")]
+    location: &'static Location<'static>,
 }
 
-impl Debug for DynHttpError {
+impl<I: IntoHttpErrorResponse> DynHttpError<I> {
+    /// Creates an error from a status code and static message, for errors
+    /// that are created very frequently. Construction doesn't allocate,
+    /// when no [ErrorConfig] or policy rewrite applies and the formatter
+    /// supports it (See `IntoHttpErrorResponse::into_static_response`) the
+    /// error isn't boxed and the static message is used as the body without
+    /// being copied. Building the response itself still allocates its
+    /// headers
+    #[track_caller]
+    pub const fn from_static(status: StatusCode, message: &'static str) -> Self {
+        Self::from_static_error(StaticHttpError::new(status, message))
+    }
+
+    /// Creates an error from a [StaticHttpError], creating the error
+    /// doesn't allocate (See [DynHttpError::from_static])
+    #[track_caller]
+    pub const fn from_static_error(error: StaticHttpError) -> Self {
+        Self::from_repr(ErrorRepr::Static(error))
+    }
+
+    /// Creates an error from any error that can be converted into an
+    /// [HttpError] (See [IntoHttpError])
+    #[track_caller]
+    pub fn from_error<E: IntoHttpError>(error: E) -> Self {
+        Self::from_repr(ErrorRepr::Boxed(error.into_http_error()))
+    }
+
+    /// Creates an error from its representation, capturing the location
+    /// of the caller when the `track-caller` feature is enabled
+    #[track_caller]
+    const fn from_repr(inner: ErrorRepr) -> Self {
+        DynHttpError {
+            inner,
+            _marker: PhantomData,
+            #[cfg(feature = "track-caller")]
+            location: Location::caller(),
+        }
+    }
+
+    /// The location in the source code where the error was converted into
+    /// a [DynHttpError] (i.e the `?` operator that produced the error)
+    #[cfg(feature = "track-caller")]
+    pub fn location(&self) -> &'static Location<'static> {
+        self.location
+    }
+
+    /// Creates an error with the provided status using the message as
+    /// the response reason
+    #[track_caller]
+    pub fn new(status: StatusCode, message: impl Into<String>) -> Self {
+        MessageHttpError::new(status, message).into()
+    }
+
+    /// Creates an error that sends the provided response as is while still
+    /// going through the logging and hooks (See [ResponseError])
+    #[cfg(feature = "axum")]
+    #[track_caller]
+    pub fn from_response(response: Response) -> Self {
+        Self::from_error(ResponseError::new(response))
+    }
+
+    /// Creates a 400 Bad Request error
+    #[track_caller]
+    pub fn bad_request(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::BAD_REQUEST, message)
+    }
+
+    /// Creates a 401 Unauthorized error
+    #[track_caller]
+    pub fn unauthorized(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::UNAUTHORIZED, message)
+    }
+
+    /// Creates a 403 Forbidden error
+    #[track_caller]
+    pub fn forbidden(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::FORBIDDEN, message)
+    }
+
+    /// Creates a 404 Not Found error
+    #[track_caller]
+    pub fn not_found(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::NOT_FOUND, message)
+    }
+
+    /// Creates a 409 Conflict error
+    #[track_caller]
+    pub fn conflict(message: impl Into<String>) -> Self {
+        Self::new(StatusCode::CONFLICT, message)
+    }
+
+    /// Creates a 500 Internal Server Error, the message is only included
+    /// in logs and the response uses a generic reason
+    #[track_caller]
+    pub fn internal(message: impl Into<String>) -> Self {
+        MessageHttpError::hidden(StatusCode::INTERNAL_SERVER_ERROR, message).into()
+    }
+
+    /// The status code of the error
+    pub fn status(&self) -> StatusCode {
+        self.inner.as_error().status()
+    }
+
+    /// The broad category of the error
+    pub fn kind(&self) -> ErrorKind {
+        self.inner.as_error().kind()
+    }
+
+    /// The gRPC status code of the error
+    pub fn grpc_code(&self) -> GrpcCode {
+        self.inner.as_error().grpc_code()
+    }
+
+    /// Replaces the status code of the error
+    pub fn map_status(self, status: StatusCode) -> Self {
+        self.rewrite(|error| error.status = Some(status))
+    }
+
+    /// Replaces the machine readable code of the error
+    pub fn with_code(self, code: impl Into<String>) -> Self {
+        let code = code.into();
+        self.rewrite(|error| error.code = Some(code))
+    }
+
+    /// Adds context describing what was happening when the error occurred
+    /// (i.e "reading chunks") which is prefixed to the message of the error
+    /// in logs, the reason included in the response is unchanged
+    pub fn context(self, context: impl Display) -> Self {
+        let context = context.to_string();
+        self.rewrite(|error| error.context = Some(context))
+    }
+
+    /// Attaches a key-value pair to the error details, values that fail
+    /// to serialize are ignored
+    #[cfg(feature = "json")]
+    pub fn attach(self, key: impl Into<String>, value: impl serde::Serialize) -> Self {
+        let Ok(value) = serde_json::to_value(value) else {
+            return self;
+        };
+        let key = key.into();
+        self.rewrite(|error| {
+            error
+                .attachments
+                .get_or_insert_with(Default::default)
+                .insert(key, value);
+        })
+    }
+
+    /// Wraps the underlying error to replace parts of it
+    fn rewrite(self, apply: impl FnOnce(&mut rewrite::RewrittenError)) -> Self {
+        let mut error = rewrite::RewrittenError::new(self.inner.into_boxed());
+        apply(&mut error);
+        DynHttpError {
+            inner: ErrorRepr::Boxed(error.into_boxed()),
+            _marker: PhantomData,
+            #[cfg(feature = "track-caller")]
+            location: self.location,
+        }
+    }
+
+    /// Attempts to downcast the underlying error into the concrete error type `T`,
+    /// when the underlying error is an [AnyhowHttpError] the types within the
+    /// anyhow error are also checked (See [anyhow::Error::downcast_ref])
+    pub fn downcast_ref<T>(&self) -> Option<&T>
+    where
+        T: Error + Send + Sync + 'static,
+    {
+        let mut error: &(dyn Error + 'static) = self.inner.as_error();
+
+        // Look through the wrappers added when enriching errors
+        while let Some(rewritten) = error.downcast_ref::<rewrite::RewrittenError>() {
+            error = rewritten.inner();
+        }
+
+        if let Some(error) = error.downcast_ref::<T>() {
+            return Some(error);
+        }
+
+        #[cfg(feature = "anyhow")]
+        if let Some(error) = error.downcast_ref::<AnyhowHttpError>() {
+            return error.error().downcast_ref::<T>();
+        }
+
+        None
+    }
+
+    /// The message of the error without the status line (See [DisplayFormat])
+    pub fn plain_message(&self) -> String {
+        self.inner.as_error().to_string()
+    }
+
+    /// Checks whether the error is a client error (4xx)
+    pub fn is_client_error(&self) -> bool {
+        self.status().is_client_error()
+    }
+
+    /// Checks whether the error is a server error (5xx)
+    pub fn is_server_error(&self) -> bool {
+        self.status().is_server_error()
+    }
+}
+
+/// Debug output includes the inner error unless [ErrorConfig::redact_debug]
+/// is enabled
+impl<I: IntoHttpErrorResponse> Debug for DynHttpError<I> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        f.debug_tuple(self.inner.type_name())
-            .field(&self.inner)
-            .finish()
+        let inner = self.inner.as_error();
+
+        #[cfg(feature = "tower")]
+        let policy = ErrorPolicy::current();
+
+        #[cfg(feature = "tower")]
+        let config = policy
+            .as_ref()
+            .and_then(|policy| policy.config_override())
+            .unwrap_or_else(ErrorConfig::current);
+        #[cfg(not(feature = "tower"))]
+        let config = ErrorConfig::current();
+
+        if !config.redact_debug {
+            return f.debug_tuple(inner.type_name()).field(&inner).finish();
+        }
+
+        // Reason as it would be exposed in the response
+        #[cfg(feature = "tower")]
+        let reason = policy
+            .as_ref()
+            .and_then(|policy| policy.redact_reason(inner))
+            .unwrap_or_else(|| inner.reason());
+        #[cfg(not(feature = "tower"))]
+        let reason = inner.reason();
+        let reason = config.truncate(&reason).unwrap_or(reason);
+
+        f.debug_struct(inner.type_name())
+            .field("status", &inner.status())
+            .field("reason", &reason)
+            .finish_non_exhaustive()
     }
 }
 
-impl Display for DynHttpError {
+/// Displays the error using the [DisplayFormat] of the current [ErrorConfig]
+impl<I: IntoHttpErrorResponse> Display for DynHttpError<I> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        Display::fmt(&self.inner, f)
+        let error = self.inner.as_error();
+        match ErrorConfig::current().display_format {
+            DisplayFormat::StatusLine => write!(f, "{}: {}", error.status(), error),
+            DisplayFormat::Message => Display::fmt(error, f),
+        }
     }
 }
 
-impl Error for DynHttpError {}
+impl<I: IntoHttpErrorResponse> Error for DynHttpError<I> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.inner.as_error().source()
+    }
 
+    /// Provides the values from the inner error such as backtraces
+    #[cfg(feature = "provide")]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        self.inner.as_error().provide(request)
+    }
+}
+
+#[cfg(feature = "axum")]
 impl<I: IntoHttpErrorResponse> IntoResponse for DynHttpError<I> {
     fn into_response(self) -> Response {
-        let error = self.inner;
+        #[cfg(feature = "tower")]
+        let context = RequestContext::current();
+        #[cfg(feature = "tower")]
+        let parts = context.as_ref().map(RequestContext::parts);
+        #[cfg(not(feature = "tower"))]
+        let parts = None;
+
+        self.into_response_with_parts(parts)
+    }
+}
+
+#[cfg(feature = "axum")]
+impl<I: IntoHttpErrorResponse> DynHttpError<I> {
+    /// Creates the response for the error using the provided request parts
+    /// instead of the parts captured by the [RequestContextLayer](crate::context::RequestContextLayer)
+    pub(crate) fn into_response_with_parts(self, parts: Option<&Parts>) -> Response {
+        // Policy attached to the current router
+        #[cfg(feature = "tower")]
+        let policy = ErrorPolicy::current();
+
+        #[cfg(feature = "tower")]
+        let config = policy
+            .as_ref()
+            .and_then(|policy| policy.config_override())
+            .unwrap_or_else(ErrorConfig::current);
+        #[cfg(not(feature = "tower"))]
+        let config = ErrorConfig::current();
+
+        let original = self.inner.as_error();
 
         // Log the error if logging is enabled
         #[cfg(feature = "log")]
         {
-            error.log();
+            let error = original;
+            let log = || match config.dedup_window {
+                Some(window) => dedup::log_deduplicated(error, window),
+                None => error.log(),
+            };
+
+            #[cfg(feature = "track-caller")]
+            location::with_location(self.location, log);
+            #[cfg(not(feature = "track-caller"))]
+            log();
         }
 
+        // Record security relevant errors and emit the error events before
+        // any rewrites are applied
+        Audit::emit(original, parts);
+        ErrorSinks::emit(original, parts);
+        let identity = ErrorIdentity::from_error(original);
+
+        // Static errors that no rewrite would change are formatted without
+        // boxing the error when the formatter supports it
+        #[cfg(feature = "tower")]
+        let rewritten = policy.is_some();
+        #[cfg(not(feature = "tower"))]
+        let rewritten = false;
+
+        let static_response = match &self.inner {
+            ErrorRepr::Static(error) if !rewritten && is_static_unchanged(error, &config) => {
+                I::into_static_response(error.status(), error.message())
+            }
+            _ => None,
+        };
+
+        // Static errors on the fast path are kept unboxed
+        let boxed = match static_response {
+            Some(_) => Err(self.inner),
+            None => {
+                // Apply any configured rewrites before the response is created
+                let error = apply_config(self.inner.into_boxed(), &config);
+
+                #[cfg(feature = "tower")]
+                let error = match &policy {
+                    Some(policy) => policy.apply(error),
+                    None => error,
+                };
+
+                Ok(error)
+            }
+        };
+        let error: &dyn HttpError = match &boxed {
+            Ok(error) => error.as_ref(),
+            Err(inner) => inner.as_error(),
+        };
+
+        // Responses provided by the error are sent as is, unless a rewrite
+        // masked the error (See [EnumerationPolicy])
+        let passthrough = error.into_response_override();
+        let is_passthrough = passthrough.is_some();
+
+        #[cfg(feature = "metrics")]
+        {
+            #[cfg(feature = "tower")]
+            let elapsed = RequestContext::current()
+                .as_ref()
+                .map(RequestContext::elapsed);
+            #[cfg(not(feature = "tower"))]
+            let elapsed = None;
+
+            metrics::record(error, elapsed);
+        }
+
+        // Collect the additional headers and extensions from the error
+        let mut headers = HeaderMap::new();
+        error.headers(&mut headers);
+        let mut extensions = Extensions::new();
+        error.extensions(&mut extensions);
+        let connection = error.connection_directive();
+        let bodiless = error.bodiless();
+        extensions.insert(connection);
+        extensions.insert(ErrorClassification {
+            kind: error.kind(),
+            transient: error.is_transient(),
+        });
+        extensions.insert(identity);
+
         // Create the HTTP response
-        I::into_response(error)
+        #[cfg(feature = "tower")]
+        let mut response = match (
+            passthrough.or(static_response),
+            boxed.ok(),
+            policy.and_then(|policy| policy.formatter_override()),
+        ) {
+            (Some(response), _, _) => response,
+            (None, Some(error), Some(formatter)) => formatter(error, parts),
+            (None, Some(error), None) => I::into_response_with_parts(error, parts),
+            (None, None, _) => unreachable!("static errors always have a response"),
+        };
+        #[cfg(not(feature = "tower"))]
+        let mut response = match (passthrough.or(static_response), boxed.ok()) {
+            (Some(response), _) => response,
+            (None, Some(error)) => I::into_response_with_parts(error, parts),
+            (None, None) => unreachable!("static errors always have a response"),
+        };
+        response.extensions_mut().extend(extensions);
+        let status = response.status();
+        let response_headers = response.headers_mut();
+        response_headers.extend(headers);
+
+        if connection == ConnectionDirective::Close {
+            response_headers.insert(CONNECTION, HeaderValue::from_static("close"));
+        }
+
+        // Errors that don't specify their own cache control use the default,
+        // except for 304 responses which must keep the caching of the resource
+        // and wrapped responses which are sent as is
+        if let Some(cache_control) = &config.cache_control {
+            if status != StatusCode::NOT_MODIFIED
+                && !is_passthrough
+                && !response_headers.contains_key(CACHE_CONTROL)
+            {
+                response_headers.insert(CACHE_CONTROL, cache_control.clone());
+            }
+        }
+
+        // Statuses that can't have a body and errors that must be sent without
+        // one are sent without the body from the formatter
+        if bodiless || status::is_bodiless_status(status) {
+            response_headers.remove(CONTENT_TYPE);
+            response_headers.remove(CONTENT_LENGTH);
+            *response.body_mut() = ().into_response().into_body();
+        }
+
+        response
     }
 }
 
+/// Checks whether none of the rewrites from the provided config would
+/// change the static error, static errors have no source, headers or
+/// details so only the status and reason can be rewritten
+#[cfg(feature = "axum")]
+fn is_static_unchanged(error: &StaticHttpError, config: &ErrorConfig) -> bool {
+    config
+        .enumeration
+        .as_ref()
+        .is_none_or(|policy| policy.rewrite(error.status()).is_none())
+        && config.truncate(error.message()).is_none()
+}
+
+/// Applies the rewrites from the provided config to the error
+#[cfg(feature = "axum")]
+fn apply_config(error: Box<dyn HttpError>, config: &ErrorConfig) -> Box<dyn HttpError> {
+    let mut error = rewrite::RewrittenError::new(error);
+
+    // Resolve the status, code and headers from the most specific error
+    if config.resolve_from_chain {
+        let resolved = ErrorChain::deepest(error.inner().source()).map(|deepest| {
+            let mut headers = HeaderMap::new();
+            deepest.headers(&mut headers);
+            (
+                deepest.status(),
+                deepest.code().map(str::to_string),
+                headers,
+            )
+        });
+
+        if let Some((status, code, headers)) = resolved {
+            error.status = Some(status);
+            error.code = code;
+            error.headers = Some(headers);
+        }
+    }
+
+    if let Some(policy) = &config.enumeration {
+        #[cfg(feature = "tower")]
+        let matches = policy.matches(RequestContext::current().as_ref().map(RequestContext::path));
+        #[cfg(not(feature = "tower"))]
+        let matches = policy.matches(None);
+
+        if matches {
+            if let Some((status, reason)) = policy.rewrite(error.status()) {
+                error.status = Some(status);
+                error.reason = Some(reason);
+                error.mask();
+            }
+        }
+    }
+
+    // Truncate reasons and detail strings that are too long
+    if config.max_reason_length.is_some() {
+        if let Some(truncated) = config.truncate(&error.reason()) {
+            error.reason = Some(truncated);
+        }
+
+        #[cfg(feature = "json")]
+        if let Some(mut details) = error.details() {
+            if config.truncate_details(&mut details) {
+                error.details = Some(details);
+            }
+        }
+    }
+
+    error.into_boxed()
+}
+
 /// Trait for implementing different response converter implementations
 /// the default is [TextErrorResponse]
+///
+/// Without the `axum` feature this is only a marker trait so that the
+/// [DynHttpError] type can be shared with crates that aren't web servers
 pub trait IntoHttpErrorResponse {
     /// Handles converting the error into an HTTP response
+    #[cfg(feature = "axum")]
     fn into_response(error: Box<dyn HttpError>) -> Response;
+
+    /// Handles converting the error into an HTTP response with access to
+    /// the parts of the request that caused the error, enabling things such
+    /// as content negotiation using the `Accept` header.
+    ///
+    /// The parts are captured by the [RequestContextLayer](crate::context::RequestContextLayer)
+    /// (`tower` feature) and will be [None] when its not in use. The default
+    /// implementation ignores the parts and uses [IntoHttpErrorResponse::into_response]
+    #[cfg(feature = "axum")]
+    fn into_response_with_parts(error: Box<dyn HttpError>, _parts: Option<&Parts>) -> Response {
+        Self::into_response(error)
+    }
+
+    /// Creates the response for a [StaticHttpError] that no configuration
+    /// or policy rewrites, from its status and static reason without boxing
+    /// the error. Must create the same response as [IntoHttpErrorResponse::into_response]
+    /// would for the error, the default returns [None] to use [IntoHttpErrorResponse::into_response]
+    #[cfg(feature = "axum")]
+    fn into_static_response(_status: StatusCode, _reason: &'static str) -> Option<Response> {
+        None
+    }
 }
 
 /// Creates HTTP errors responses where the "reason" is provided as
@@ -119,9 +969,15 @@ pub trait IntoHttpErrorResponse {
 pub struct TextErrorResponse;
 
 impl IntoHttpErrorResponse for TextErrorResponse {
+    #[cfg(feature = "axum")]
     fn into_response(error: Box<dyn HttpError>) -> Response {
         (error.status(), error.reason()).into_response()
     }
+
+    #[cfg(feature = "axum")]
+    fn into_static_response(status: StatusCode, reason: &'static str) -> Option<Response> {
+        Some((status, reason).into_response())
+    }
 }
 
 /// This trait should be implemented by error types that can be used
@@ -130,18 +986,24 @@ pub trait HttpError: Error + Send + Sync + 'static {
     /// Handles logging the error when its translated into an HTTP error response
     ///
     /// Default implementation logs both the [Display] and [Debug] variants
-    /// of the error
+    /// of the error, with the `track-caller` feature the location where
+    /// the error was converted into a [DynHttpError] is also included
     #[cfg(feature = "log")]
     fn log(&self) {
-        log::error!("{self}: {self:?}");
+        log_default(self)
     }
 
     /// Handles determining the HTTP status code that should be used
     /// for the HTTP response
     ///
-    /// Defaults to [StatusCode::INTERNAL_SERVER_ERROR]
+    /// Defaults to the status of the [Error::source] when its a [HttpError]
+    /// registered with [ErrorChain] (i.e the inner error of a thiserror
+    /// `#[from]` variant) otherwise [StatusCode::INTERNAL_SERVER_ERROR]
     fn status(&self) -> StatusCode {
-        StatusCode::INTERNAL_SERVER_ERROR
+        match self.source().and_then(ErrorChain::as_http_error) {
+            Some(source) => source.status(),
+            None => StatusCode::INTERNAL_SERVER_ERROR,
+        }
     }
 
     /// Handles creating the error response "reason" text that
@@ -150,6 +1012,129 @@ pub trait HttpError: Error + Send + Sync + 'static {
         self.to_string()
     }
 
+    /// Handles adding any additional headers that should be included
+    /// in the error response.
+    ///
+    /// Errors that can be cached (i.e a stable 410 Gone) can provide their
+    /// own `Cache-Control` header here which will be used instead of the
+    /// default from [ErrorConfig::cache_control]
+    ///
+    /// Defaults to the headers of the [Error::source] when its a [HttpError]
+    /// registered with [ErrorChain]
+    fn headers(&self, headers: &mut HeaderMap) {
+        if let Some(source) = self.source().and_then(ErrorChain::as_http_error) {
+            source.headers(headers);
+        }
+    }
+
+    /// Handles adding typed data to the extensions of the error response,
+    /// allowing errors to pass information to downstream middleware (i.e
+    /// rate limiting information or audit events)
+    fn extensions(&self, _extensions: &mut Extensions) {}
+
+    /// Provides a machine readable code identifying the error (i.e
+    /// `USER_NOT_FOUND`) that is included by structured formatters
+    ///
+    /// Defaults to the code of the [Error::source] when its a [HttpError]
+    /// registered with [ErrorChain]
+    fn code(&self) -> Option<&str> {
+        self.source()
+            .and_then(ErrorChain::as_http_error)
+            .and_then(|source| source.code())
+    }
+
+    /// Provides advice for the client on how to resolve the error (i.e
+    /// "Try reducing the page size") that is included by structured
+    /// formatters and the debug information, rather than including the
+    /// advice in the reason
+    ///
+    /// Defaults to [None]
+    fn help(&self) -> Option<String> {
+        None
+    }
+
+    /// Provides the steps for remediating the error when the client can't
+    /// resolve it by changing the request (i.e "Contact support with the
+    /// request ID") that are included by structured formatters and the
+    /// debug information
+    ///
+    /// Defaults to [None]
+    fn remediation(&self) -> Option<String> {
+        None
+    }
+
+    /// Provides the broad category of the error which is included by
+    /// structured formatters and in logs
+    ///
+    /// Defaults to the kind determined from the [HttpError::status]
+    fn kind(&self) -> ErrorKind {
+        ErrorKind::from_status(self.status())
+    }
+
+    /// Provides the gRPC status code of the error for services that share
+    /// their errors between HTTP and gRPC (See the `tonic` feature)
+    ///
+    /// Defaults to the code determined from the [HttpError::status]
+    fn grpc_code(&self) -> GrpcCode {
+        GrpcCode::from_status(self.status())
+    }
+
+    /// Directive for the connection the error response is sent on, errors
+    /// can request the connection is closed when the instance is unhealthy
+    /// so that load balancers move clients to another instance
+    ///
+    /// Defaults to [ConnectionDirective::Keep]
+    fn connection_directive(&self) -> ConnectionDirective {
+        ConnectionDirective::Keep
+    }
+
+    /// Whether the error is security relevant, security relevant errors
+    /// are sent to the registered [AuditSink]s (See [Audit])
+    ///
+    /// Defaults to true for 401 and 403 status codes
+    fn security_relevant(&self) -> bool {
+        status::is_security_relevant_status(self.status())
+    }
+
+    /// Whether the error is transient and the request may succeed if retried
+    /// later, used for circuit breaking and load shedding decisions (See
+    /// [TransientFailureLayer](crate::transient::TransientFailureLayer))
+    ///
+    /// Defaults to true for 408, 429, 502, 503 and 504 status codes
+    fn is_transient(&self) -> bool {
+        status::is_transient_status(self.status())
+    }
+
+    /// Whether the error response must be sent without a body, the body
+    /// from the formatter is dropped along with its `Content-Type` (i.e
+    /// 412 Precondition Failed responses to conditional requests)
+    ///
+    /// Defaults to true for 1xx, 204 and 304 status codes, responses with
+    /// these statuses never have a body regardless of this hook
+    fn bodiless(&self) -> bool {
+        status::is_bodiless_status(self.status())
+    }
+
+    /// Provides additional structured details about the error that are
+    /// included by structured formatters such as [JsonErrorResponse]
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        None
+    }
+
+    /// Creates the response for the error instead of the formatter, for
+    /// errors that need full control over their response (i.e a
+    /// `text/event-stream` or binary body). The error still goes through
+    /// the logging, hooks and metrics but the response is sent as is
+    /// without the default `Cache-Control` header
+    ///
+    /// Defaults to [None] using the formatter
+    #[cfg(feature = "axum")]
+    #[allow(clippy::wrong_self_convention)]
+    fn into_response_override(&self) -> Option<Response> {
+        None
+    }
+
     /// Provides the full type name for the actual error type thats been
     /// erased by dynamic typing (For better error source clarity) used by
     /// the [Debug] implementation of [DynHttpError]
@@ -158,16 +1143,130 @@ pub trait HttpError: Error + Send + Sync + 'static {
     }
 }
 
+impl dyn HttpError {
+    /// Attempts to downcast the error into the concrete error type `T`,
+    /// looking through any number of wrappers added when enriching or
+    /// rewriting the error
+    pub fn downcast_ref<T: HttpError>(&self) -> Option<&T> {
+        let mut error: &(dyn Error + 'static) = self;
+        loop {
+            if let Some(error) = error.downcast_ref::<T>() {
+                return Some(error);
+            }
+
+            // Look through the wrappers used when rewriting errors
+            error = error.downcast_ref::<rewrite::RewrittenError>()?.inner();
+        }
+    }
+}
+
+/// Logs the error using the rule of the default [HttpError::log]
+#[cfg(feature = "log")]
+pub(crate) fn log_default<E: HttpError + ?Sized>(error: &E) {
+    log::error!(
+        "[{}] {error}: {error:?}{}",
+        error.kind(),
+        location::log_suffix()
+    );
+}
+
 /// Allow conversion from implementors of [HttpError] into a [DynHttpError]
 impl<E, I> From<E> for DynHttpError<I>
 where
     E: HttpError,
     I: IntoHttpErrorResponse,
 {
+    #[track_caller]
     fn from(value: E) -> Self {
-        DynHttpError {
-            inner: Box::new(value),
-            _marker: PhantomData,
+        Self::from_repr(ErrorRepr::Boxed(Box::new(value)))
+    }
+}
+
+/// Allow conversion from [Infallible] so that generic code bounded on
+/// `E: Into<DynHttpError>` works with infallible results
+impl<I> From<Infallible> for DynHttpError<I>
+where
+    I: IntoHttpErrorResponse,
+{
+    fn from(value: Infallible) -> Self {
+        match value {}
+    }
+}
+
+/// Allow conversion into an [axum_core::Error] so that the error can be passed
+/// through middleware that requires errors to be [axum_core::BoxError]
+/// (Conversion into [axum_core::BoxError] is provided by the [Error] impl)
+#[cfg(feature = "axum")]
+impl<I> From<DynHttpError<I>> for axum_core::Error
+where
+    I: IntoHttpErrorResponse + 'static,
+{
+    fn from(value: DynHttpError<I>) -> Self {
+        axum_core::Error::new(value)
+    }
+}
+
+/// Best-effort conversion from a boxed error back into a [DynHttpError],
+/// succeeds if the boxed error is a [DynHttpError] or one of the [HttpError] types
+/// registered with [ErrorChain] otherwise the original error is returned
+impl<I> TryFrom<Box<dyn Error + Send + Sync>> for DynHttpError<I>
+where
+    I: IntoHttpErrorResponse + 'static,
+{
+    type Error = Box<dyn Error + Send + Sync>;
+
+    #[track_caller]
+    fn try_from(value: Box<dyn Error + Send + Sync>) -> Result<Self, Self::Error> {
+        let value = match value.downcast::<DynHttpError<I>>() {
+            Ok(error) => return Ok(*error),
+            Err(value) => value,
+        };
+
+        match ErrorChain::downcast_boxed(value) {
+            Ok(error) => Ok(Self::from_repr(ErrorRepr::Boxed(error))),
+            Err(value) => Err(value),
         }
     }
 }
+
+/// Best-effort conversion from an [axum_core::Error] back into a [DynHttpError]
+/// (See the boxed error conversion)
+#[cfg(feature = "axum")]
+impl<I> TryFrom<axum_core::Error> for DynHttpError<I>
+where
+    I: IntoHttpErrorResponse + 'static,
+{
+    type Error = axum_core::Error;
+
+    fn try_from(value: axum_core::Error) -> Result<Self, Self::Error> {
+        DynHttpError::try_from(value.into_inner()).map_err(axum_core::Error::new)
+    }
+}
+
+/// Allow conversion from [String] messages into a [DynHttpError] for
+/// prototyping, the message is logged and the response is a generic
+/// 500 Internal Server Error
+#[cfg(feature = "string-errors")]
+impl<I> From<String> for DynHttpError<I>
+where
+    I: IntoHttpErrorResponse,
+{
+    #[track_caller]
+    fn from(value: String) -> Self {
+        MessageHttpError::hidden(StatusCode::INTERNAL_SERVER_ERROR, value).into()
+    }
+}
+
+/// Allow conversion from static string messages into a [DynHttpError] for
+/// prototyping, the message is logged and the response is a generic
+/// 500 Internal Server Error
+#[cfg(feature = "string-errors")]
+impl<I> From<&'static str> for DynHttpError<I>
+where
+    I: IntoHttpErrorResponse,
+{
+    #[track_caller]
+    fn from(value: &'static str) -> Self {
+        MessageHttpError::hidden(StatusCode::INTERNAL_SERVER_ERROR, value).into()
+    }
+}
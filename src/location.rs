@@ -0,0 +1,35 @@
+//! Location of the error that is currently being logged, allowing the
+//! [HttpError::log](crate::HttpError::log) implementations to include
+//! where the error entered the HTTP layer
+
+#[cfg(feature = "track-caller")]
+use std::cell::Cell;
+#[cfg(feature = "track-caller")]
+use std::panic::Location;
+
+#[cfg(feature = "track-caller")]
+thread_local! {
+    /// Location of the error currently being logged on this thread
+    static LOGGING_LOCATION: Cell<Option<&'static Location<'static>>> = const { Cell::new(None) };
+}
+
+/// Runs the provided logging function with the location set as the
+/// location of the error being logged
+#[cfg(feature = "track-caller")]
+pub(crate) fn with_location<R>(location: &'static Location<'static>, log: impl FnOnce() -> R) -> R {
+    let previous = LOGGING_LOCATION.with(|current| current.replace(Some(location)));
+    let result = log();
+    LOGGING_LOCATION.with(|current| current.set(previous));
+    result
+}
+
+/// Suffix for log messages containing the location of the error currently
+/// being logged, empty when the location isn't known
+pub(crate) fn log_suffix() -> String {
+    #[cfg(feature = "track-caller")]
+    if let Some(location) = LOGGING_LOCATION.with(Cell::get) {
+        return format!(" (at {location})");
+    }
+
+    String::new()
+}
@@ -0,0 +1,73 @@
+//! Support for the errors of `axum-login`
+//!
+//! Session failures use the status of the [tower_sessions] error (503 for
+//! session store outages) and backend failures are 500 Internal Server
+//! Errors unless the backend error is a [HttpError] registered with the
+//! [ErrorChain]. [AuthSessionExt] provides 401 errors for handlers that
+//! require an authenticated user
+
+use axum_login::{AuthSession, AuthnBackend, Error};
+use http::StatusCode;
+
+use crate::{ErrorChain, HttpError, MessageHttpError};
+
+impl<B> HttpError for Error<B>
+where
+    B: AuthnBackend + 'static,
+    B::Error: 'static,
+{
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        match self {
+            Error::Session(error) => error.log(),
+            Error::Backend(error) => match ErrorChain::as_http_error(error) {
+                Some(error) => error.log(),
+                None => log::error!("{self}: {self:?}{}", crate::location::log_suffix()),
+            },
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            Error::Session(error) => error.status(),
+            Error::Backend(error) => ErrorChain::as_http_error(error)
+                .map(HttpError::status)
+                .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+        }
+    }
+
+    fn reason(&self) -> String {
+        match self {
+            Error::Session(error) => error.reason(),
+            Error::Backend(error) => ErrorChain::as_http_error(error)
+                .map(HttpError::reason)
+                .unwrap_or_else(|| "Server error".to_string()),
+        }
+    }
+}
+
+/// Extension for requiring an authenticated user from an [AuthSession],
+/// failed permission checks can use [DynHttpError::forbidden](crate::DynHttpError::forbidden)
+///
+/// ```
+/// use axum_dyn_error::{AuthSessionExt, HttpResult};
+/// use axum_login::{AuthSession, AuthUser, AuthnBackend};
+///
+/// pub async fn profile<B: AuthnBackend>(auth_session: AuthSession<B>) -> HttpResult<String> {
+///     let user = auth_session.require_user()?;
+///     Ok(format!("{:?}", user.id()))
+/// }
+/// ```
+pub trait AuthSessionExt<B: AuthnBackend> {
+    /// Obtains the authenticated user, responding with a 401 Unauthorized
+    /// error when the session isn't authenticated
+    fn require_user(&self) -> Result<B::User, MessageHttpError>;
+}
+
+impl<B: AuthnBackend> AuthSessionExt<B> for AuthSession<B> {
+    fn require_user(&self) -> Result<B::User, MessageHttpError> {
+        self.user
+            .clone()
+            .ok_or_else(|| MessageHttpError::new(StatusCode::UNAUTHORIZED, "Unauthorized"))
+    }
+}
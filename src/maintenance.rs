@@ -0,0 +1,133 @@
+//! Layer for putting a router into maintenance mode
+
+use std::marker::PhantomData;
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+
+use axum_core::response::{IntoResponse, Response};
+use futures_util::future::{ready, Either, Ready};
+use http::Request;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{DynHttpError, IntoHttpErrorResponse, ServiceUnavailable, TextErrorResponse};
+
+/// Shared handle for toggling maintenance mode at runtime, clones of
+/// the handle share the same state
+#[derive(Debug, Clone, Default)]
+pub struct MaintenanceMode {
+    /// The error to respond with while in maintenance mode
+    state: Arc<RwLock<Option<ServiceUnavailable>>>,
+}
+
+impl MaintenanceMode {
+    /// Creates a new handle with maintenance mode disabled
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Enables maintenance mode, responding to all requests with
+    /// the provided error
+    pub fn enable(&self, error: ServiceUnavailable) {
+        *self.state.write().unwrap_or_else(|err| err.into_inner()) = Some(error);
+    }
+
+    /// Disables maintenance mode
+    pub fn disable(&self) {
+        *self.state.write().unwrap_or_else(|err| err.into_inner()) = None;
+    }
+
+    /// Checks whether maintenance mode is enabled
+    pub fn is_enabled(&self) -> bool {
+        self.current().is_some()
+    }
+
+    /// Obtains a copy of the error if maintenance mode is enabled
+    fn current(&self) -> Option<ServiceUnavailable> {
+        self.state
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+}
+
+/// Layer that responds to all requests with a [ServiceUnavailable] error
+/// while its [MaintenanceMode] is enabled
+pub struct MaintenanceLayer<I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// The maintenance mode state
+    mode: MaintenanceMode,
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<I: IntoHttpErrorResponse> MaintenanceLayer<I> {
+    /// Creates a new layer using the provided maintenance mode handle
+    pub fn new(mode: MaintenanceMode) -> Self {
+        Self {
+            mode,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: IntoHttpErrorResponse> Clone for MaintenanceLayer<I> {
+    fn clone(&self) -> Self {
+        Self::new(self.mode.clone())
+    }
+}
+
+impl<S, I: IntoHttpErrorResponse> Layer<S> for MaintenanceLayer<I> {
+    type Service = MaintenanceService<S, I>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        MaintenanceService {
+            inner,
+            mode: self.mode.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Service created by [MaintenanceLayer]
+pub struct MaintenanceService<S, I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// The inner service
+    inner: S,
+    /// The maintenance mode state
+    mode: MaintenanceMode,
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<S: Clone, I: IntoHttpErrorResponse> Clone for MaintenanceService<S, I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            mode: self.mode.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, B, I> Service<Request<B>> for MaintenanceService<S, I>
+where
+    S: Service<Request<B>, Response = Response>,
+    I: IntoHttpErrorResponse,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        match self.mode.current() {
+            Some(error) => {
+                let response = DynHttpError::<I>::from(error).into_response();
+                Either::Left(ready(Ok(response)))
+            }
+            None => Either::Right(self.inner.call(request)),
+        }
+    }
+}
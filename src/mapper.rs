@@ -0,0 +1,101 @@
+//! Global registry for mapping foreign error types to HTTP statuses
+
+use std::error::Error;
+use std::sync::{Arc, RwLock};
+
+use http::StatusCode;
+
+/// Type of the mapping functions stored by the registry
+type MapperFn = Arc<dyn Fn(&(dyn Error + 'static)) -> Option<ErrorMapping> + Send + Sync>;
+
+/// Registered mapping functions, replaced as a whole when registering so
+/// that mappers are called without holding the lock
+static MAPPERS: RwLock<Option<Arc<[MapperFn]>>> = RwLock::new(None);
+
+/// Result of mapping a foreign error type
+#[derive(Debug, Clone)]
+pub struct ErrorMapping {
+    /// The status code to use for the error
+    pub status: StatusCode,
+    /// Optional public reason to use instead of the default
+    pub reason: Option<String>,
+}
+
+impl ErrorMapping {
+    /// Creates a mapping using the provided status
+    pub fn new(status: StatusCode) -> Self {
+        Self {
+            status,
+            reason: None,
+        }
+    }
+
+    /// Sets the public reason for the mapping
+    pub fn reason(mut self, reason: impl Into<String>) -> Self {
+        self.reason = Some(reason.into());
+        self
+    }
+}
+
+/// Registry of mappers that are consulted when converting errors that
+/// don't implement [HttpError](crate::HttpError) such as `anyhow` errors
+/// and boxed errors, allowing foreign error types deep within the error
+/// chain to be mapped to the correct status without wrapping them at
+/// every call site
+pub struct ErrorMapper;
+
+impl ErrorMapper {
+    /// Registers a mapper function that is called for every error in the
+    /// error chain
+    pub fn register<F>(mapper: F)
+    where
+        F: Fn(&(dyn Error + 'static)) -> Option<ErrorMapping> + Send + Sync + 'static,
+    {
+        let mut mappers = MAPPERS.write().unwrap_or_else(|err| err.into_inner());
+        let existing = mappers.as_deref().unwrap_or_default();
+        let mapper: MapperFn = Arc::new(mapper);
+        *mappers = Some(existing.iter().cloned().chain([mapper]).collect());
+    }
+
+    /// Registers a mapper function for a specific error type
+    ///
+    /// ```
+    /// use axum_dyn_error::{ErrorMapper, ErrorMapping, StatusCode};
+    ///
+    /// ErrorMapper::register_type(|_: &std::num::ParseIntError| {
+    ///     Some(ErrorMapping::new(StatusCode::BAD_REQUEST))
+    /// });
+    /// ```
+    pub fn register_type<E, F>(mapper: F)
+    where
+        E: Error + 'static,
+        F: Fn(&E) -> Option<ErrorMapping> + Send + Sync + 'static,
+    {
+        Self::register(move |error| error.downcast_ref::<E>().and_then(&mapper))
+    }
+
+    /// Removes all the registered mappers
+    pub fn clear() {
+        *MAPPERS.write().unwrap_or_else(|err| err.into_inner()) = None;
+    }
+
+    /// Finds the first mapping for the provided error or any of
+    /// its sources, mappers registered while mapping are only used by
+    /// later calls
+    pub fn map(error: &(dyn Error + 'static)) -> Option<ErrorMapping> {
+        let mappers = MAPPERS
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()?;
+
+        let mut current = Some(error);
+        while let Some(error) = current {
+            if let Some(mapping) = mappers.iter().find_map(|mapper| mapper(error)) {
+                return Some(mapping);
+            }
+            current = error.source();
+        }
+
+        None
+    }
+}
@@ -0,0 +1,41 @@
+//! Support for the errors of message brokers (`lapin` for AMQP and
+//! `async-nats` for NATS)
+//!
+//! Connection and publish failures of the broker are 503 Service
+//! Unavailable errors classified as transient, for HTTP frontends that
+//! forward requests over a message queue. Responses use a generic reason
+//! and the broker error is only included in logs
+
+use http::StatusCode;
+
+use crate::HttpError;
+
+/// Reason used for broker failures
+const BROKER_UNAVAILABLE: &str = "Message broker unavailable";
+
+#[cfg(feature = "lapin")]
+impl HttpError for lapin::Error {
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+
+    fn reason(&self) -> String {
+        BROKER_UNAVAILABLE.to_string()
+    }
+}
+
+/// Covers the NATS errors such as `ConnectError`, `PublishError`,
+/// `RequestError` and `SubscribeError`
+#[cfg(feature = "async-nats")]
+impl<K> HttpError for async_nats::error::Error<K>
+where
+    K: Clone + std::fmt::Debug + std::fmt::Display + PartialEq + Send + Sync + 'static,
+{
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+
+    fn reason(&self) -> String {
+        BROKER_UNAVAILABLE.to_string()
+    }
+}
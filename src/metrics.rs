@@ -0,0 +1,194 @@
+//! Error metrics recorded using the [metrics] crate
+
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, RwLock};
+#[cfg(feature = "axum")]
+use std::time::Duration;
+
+#[cfg(feature = "axum")]
+use crate::HttpError;
+
+/// Name of the counter for error responses, labeled with the
+/// `status` code and `kind` of the error and the `type` of the error
+/// when [TypeLabels] are installed
+pub const ERRORS_TOTAL: &str = "http_errors_total";
+
+/// Label used for error types that aren't allowed or exceed the limit
+/// of distinct types
+pub const OTHER_TYPE_LABEL: &str = "other";
+
+/// Currently installed type label configuration
+static TYPE_LABELS: RwLock<Option<Arc<TypeLabels>>> = RwLock::new(None);
+
+/// Abbreviates a type name for use as a label by removing the generic
+/// parameters and module paths (i.e `tokio::time::error::Elapsed<T>` becomes
+/// `Elapsed`)
+pub fn sanitize_type_name(type_name: &str) -> String {
+    let name = type_name
+        .split_once('<')
+        .map_or(type_name, |(name, _)| name)
+        .trim();
+    let name = name.rsplit("::").next().unwrap_or(name);
+
+    name.chars()
+        .filter(|char| char.is_ascii_alphanumeric() || *char == '_')
+        .collect()
+}
+
+/// Configuration for the `type` label of the [ERRORS_TOTAL] counter, the
+/// label is only added once the configuration is installed using
+/// [TypeLabels::install]
+///
+/// Type names are abbreviated using [sanitize_type_name] and the number of
+/// distinct labels is limited to keep the cardinality of the metric low,
+/// types beyond the limit or outside the allowlist use [OTHER_TYPE_LABEL]
+///
+/// ```
+/// use axum_dyn_error::metrics::TypeLabels;
+///
+/// TypeLabels::new()
+///     .max_types(20)
+///     .allow("NotFound")
+///     .allow("SqlxError")
+///     .install();
+/// ```
+#[derive(Debug)]
+pub struct TypeLabels {
+    /// Sanitized type names that are allowed, [None] allows any type
+    allowlist: Option<HashSet<String>>,
+    /// Maximum number of distinct type labels
+    max_types: usize,
+    /// Type labels that have been recorded
+    seen: Mutex<HashSet<String>>,
+}
+
+impl Default for TypeLabels {
+    fn default() -> Self {
+        Self {
+            allowlist: None,
+            max_types: 50,
+            seen: Mutex::new(HashSet::new()),
+        }
+    }
+}
+
+impl TypeLabels {
+    /// Creates a new configuration allowing up to 50 distinct types
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of distinct type labels (Default: 50)
+    pub fn max_types(mut self, max_types: usize) -> Self {
+        self.max_types = max_types;
+        self
+    }
+
+    /// Adds a type to the allowlist, once any type is added only the
+    /// allowed types are used as labels. The name is sanitized using
+    /// [sanitize_type_name] so full type names can be provided
+    pub fn allow(mut self, type_name: &str) -> Self {
+        self.allowlist
+            .get_or_insert_with(HashSet::new)
+            .insert(sanitize_type_name(type_name));
+        self
+    }
+
+    /// Installs this configuration replacing any previously installed
+    /// configuration
+    pub fn install(self) {
+        *TYPE_LABELS.write().unwrap_or_else(|err| err.into_inner()) = Some(Arc::new(self));
+    }
+
+    /// Removes the installed configuration, the `type` label is no
+    /// longer added
+    pub fn uninstall() {
+        *TYPE_LABELS.write().unwrap_or_else(|err| err.into_inner()) = None;
+    }
+
+    /// Obtains the currently installed configuration
+    pub fn current() -> Option<Arc<TypeLabels>> {
+        TYPE_LABELS
+            .read()
+            .unwrap_or_else(|err| err.into_inner())
+            .clone()
+    }
+
+    /// Determines the label for the provided type name
+    pub fn label(&self, type_name: &str) -> String {
+        let name = sanitize_type_name(type_name);
+        if name.is_empty()
+            || self
+                .allowlist
+                .as_ref()
+                .is_some_and(|allowlist| !allowlist.contains(&name))
+        {
+            return OTHER_TYPE_LABEL.to_string();
+        }
+
+        let mut seen = self.seen.lock().unwrap_or_else(|err| err.into_inner());
+        if seen.contains(&name) {
+            return name;
+        }
+
+        if seen.len() >= self.max_types {
+            return OTHER_TYPE_LABEL.to_string();
+        }
+
+        seen.insert(name.clone());
+        name
+    }
+}
+
+/// Name of the counter for transient error responses (See [HttpError::is_transient]),
+/// labeled with the `status` code of the error
+pub const TRANSIENT_ERRORS_TOTAL: &str = "http_transient_errors_total";
+
+/// Name of the histogram for the time in seconds from the start of the
+/// request until the error was converted into a response, labeled with
+/// the `status` code of the error. Requires the `tower` feature and the
+/// [RequestContextLayer](crate::context::RequestContextLayer)
+pub const ERROR_DURATION_SECONDS: &str = "http_error_duration_seconds";
+
+/// Registers the descriptions of the error metrics with the installed
+/// recorder, call this after installing the recorder
+pub fn describe_metrics() {
+    metrics::describe_counter!(ERRORS_TOTAL, "Number of HTTP error responses");
+    metrics::describe_counter!(
+        TRANSIENT_ERRORS_TOTAL,
+        "Number of transient HTTP error responses"
+    );
+    metrics::describe_histogram!(
+        ERROR_DURATION_SECONDS,
+        metrics::Unit::Seconds,
+        "Time from the start of the request until the error response"
+    );
+}
+
+/// Records the metrics for an error response, the elapsed time is
+/// only known when the request context is available
+#[cfg(feature = "axum")]
+pub(crate) fn record(error: &dyn HttpError, elapsed: Option<Duration>) {
+    let status = error.status().as_u16().to_string();
+
+    if let Some(elapsed) = elapsed {
+        metrics::histogram!(ERROR_DURATION_SECONDS, "status" => status.clone()).record(elapsed);
+    }
+
+    if error.is_transient() {
+        metrics::counter!(TRANSIENT_ERRORS_TOTAL, "status" => status.clone()).increment(1);
+    }
+
+    let mut labels = vec![
+        metrics::Label::new("status", status),
+        metrics::Label::new("kind", error.kind().as_str()),
+    ];
+    if let Some(type_labels) = TypeLabels::current() {
+        labels.push(metrics::Label::new(
+            "type",
+            type_labels.label(error.type_name()),
+        ));
+    }
+
+    metrics::counter!(ERRORS_TOTAL, labels).increment(1);
+}
@@ -0,0 +1,133 @@
+//! Middleware created from async functions that return a [DynHttpError],
+//! errors are converted into responses using the formatter of the error
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum_core::response::{IntoResponse, Response};
+use futures_util::future::poll_fn;
+use http::Request;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{DynHttpError, IntoHttpErrorResponse};
+
+/// Boxed future used by the middleware
+type BoxFuture<T> = Pin<Box<dyn Future<Output = T> + Send>>;
+
+/// Creates a middleware layer from an async function that can fail with a
+/// [DynHttpError], similar to `axum::middleware::from_fn`. Errors returned
+/// by the function are rendered using the [IntoHttpErrorResponse] of the
+/// error so that middleware responds with the same error bodies as handlers
+///
+/// ```
+/// use axum::{response::Response, routing::get, Router};
+/// use axum_dyn_error::middleware::{from_fn_dyn_error, Next};
+/// use axum_dyn_error::{DynHttpError, HttpResult};
+/// use http::{header::AUTHORIZATION, Request};
+///
+/// async fn require_auth<B>(request: Request<B>, next: Next<B>) -> HttpResult<Response> {
+///     if !request.headers().contains_key(AUTHORIZATION) {
+///         return Err(DynHttpError::unauthorized("Missing authorization"));
+///     }
+///     Ok(next.run(request).await)
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .layer(from_fn_dyn_error(require_auth));
+/// ```
+pub fn from_fn_dyn_error<F>(f: F) -> FromFnDynErrorLayer<F> {
+    FromFnDynErrorLayer { f }
+}
+
+/// The remainder of the middleware stack, passed to the function of a
+/// [from_fn_dyn_error] middleware
+pub struct Next<B> {
+    /// Function running the inner service
+    run: Box<dyn FnOnce(Request<B>) -> BoxFuture<Response> + Send>,
+}
+
+impl<B> Next<B> {
+    /// Runs the remainder of the middleware stack with the provided request
+    pub async fn run(self, request: Request<B>) -> Response {
+        (self.run)(request).await
+    }
+}
+
+/// Layer created by [from_fn_dyn_error]
+#[derive(Clone)]
+pub struct FromFnDynErrorLayer<F> {
+    /// The middleware function
+    f: F,
+}
+
+impl<S, F: Clone> Layer<S> for FromFnDynErrorLayer<F> {
+    type Service = FromFnDynErrorService<S, F>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        FromFnDynErrorService {
+            inner,
+            f: self.f.clone(),
+        }
+    }
+}
+
+/// Service created by [FromFnDynErrorLayer]
+#[derive(Clone)]
+pub struct FromFnDynErrorService<S, F> {
+    /// The inner service
+    inner: S,
+    /// The middleware function
+    f: F,
+}
+
+impl<S, F, Fut, B, I> Service<Request<B>> for FromFnDynErrorService<S, F>
+where
+    S: Service<Request<B>, Response = Response, Error = Infallible> + Clone + Send + 'static,
+    S::Future: Send,
+    F: FnMut(Request<B>, Next<B>) -> Fut,
+    Fut: Future<Output = Result<Response, DynHttpError<I>>> + Send + 'static,
+    B: Send + 'static,
+    I: IntoHttpErrorResponse,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = BoxFuture<Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // The inner service is polled for readiness when the function runs it
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        // Take the service that was driven to readiness leaving a clone behind
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        let next = Next {
+            run: Box::new(move |request| {
+                Box::pin(async move {
+                    let result = match poll_fn(|cx| inner.poll_ready(cx)).await {
+                        Ok(()) => inner.call(request).await,
+                        Err(err) => Err(err),
+                    };
+                    match result {
+                        Ok(response) => response,
+                        Err(err) => match err {},
+                    }
+                })
+            }),
+        };
+
+        let future = (self.f)(request, next);
+        Box::pin(async move {
+            Ok(match future.await {
+                Ok(response) => response,
+                Err(error) => error.into_response(),
+            })
+        })
+    }
+}
@@ -0,0 +1,170 @@
+//! Formatter choosing between HTML, JSON and text responses based on the
+//! `Accept` header of the request with configurable fallbacks and overrides
+
+use std::marker::PhantomData;
+
+use axum_core::response::Response;
+use http::{header::ACCEPT, request::Parts};
+
+use crate::{
+    HtmlErrorResponse, HttpError, IntoHttpErrorResponse, JsonErrorResponse, TextErrorResponse,
+};
+
+/// Response formats the [Negotiated] formatter can choose between
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResponseFormat {
+    /// HTML error page (See [HtmlErrorResponse])
+    Html,
+    /// JSON error body (See [JsonErrorResponse])
+    Json,
+    /// Plain text error body (See [TextErrorResponse])
+    Text,
+}
+
+impl ResponseFormat {
+    /// The media type of the format
+    pub fn media_type(&self) -> &'static str {
+        match self {
+            ResponseFormat::Html => "text/html",
+            ResponseFormat::Json => "application/json",
+            ResponseFormat::Text => "text/plain",
+        }
+    }
+
+    /// Creates the response for the error using the format
+    fn into_response(self, error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        match self {
+            ResponseFormat::Html => HtmlErrorResponse::into_response_with_parts(error, parts),
+            ResponseFormat::Json => <JsonErrorResponse>::into_response_with_parts(error, parts),
+            ResponseFormat::Text => TextErrorResponse::into_response_with_parts(error, parts),
+        }
+    }
+}
+
+/// Rules used by the [Negotiated] formatter
+pub trait NegotiationRules {
+    /// Formats that can be chosen in order of preference, used to break
+    /// ties between equally acceptable formats and the first format is
+    /// used when the request doesn't have an `Accept` header or doesn't
+    /// accept any of the formats
+    const FALLBACK: &'static [ResponseFormat] = &[
+        ResponseFormat::Json,
+        ResponseFormat::Html,
+        ResponseFormat::Text,
+    ];
+
+    /// Format to use regardless of the `Accept` header (i.e always HTML
+    /// for 404 errors on web routes or always JSON for `/api` routes),
+    /// returns [None] to negotiate the format
+    fn override_format(_error: &dyn HttpError, _parts: Option<&Parts>) -> Option<ResponseFormat> {
+        None
+    }
+}
+
+/// [NegotiationRules] that negotiate between JSON, HTML and text in
+/// that order without any overrides
+pub struct DefaultNegotiation;
+
+impl NegotiationRules for DefaultNegotiation {}
+
+/// Formatter that chooses the response format using the `Accept` header of
+/// the request (Requires the request parts to be captured by the `RequestContextLayer`)
+/// and the provided [NegotiationRules]
+///
+/// ```
+/// use axum_dyn_error::{HttpError, HttpResult, Negotiated, NegotiationRules, ResponseFormat};
+/// use axum_dyn_error::StatusCode;
+/// use http::request::Parts;
+///
+/// pub struct AppRules;
+///
+/// impl NegotiationRules for AppRules {
+///     const FALLBACK: &'static [ResponseFormat] = &[ResponseFormat::Html, ResponseFormat::Json];
+///
+///     fn override_format(error: &dyn HttpError, parts: Option<&Parts>) -> Option<ResponseFormat> {
+///         let path = parts.map(|parts| parts.uri.path()).unwrap_or_default();
+///         if path.starts_with("/api") {
+///             return Some(ResponseFormat::Json);
+///         }
+///         (error.status() == StatusCode::NOT_FOUND).then_some(ResponseFormat::Html)
+///     }
+/// }
+///
+/// pub type MyHttpResult<T> = HttpResult<T, Negotiated<AppRules>>;
+/// ```
+pub struct Negotiated<R = DefaultNegotiation> {
+    /// Marker for storing the rules type
+    _marker: PhantomData<fn() -> R>,
+}
+
+impl<R: NegotiationRules> IntoHttpErrorResponse for Negotiated<R> {
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        Self::into_response_with_parts(error, None)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        let format = R::override_format(error.as_ref(), parts).unwrap_or_else(|| {
+            let accept = parts
+                .and_then(|parts| parts.headers.get(ACCEPT))
+                .and_then(|value| value.to_str().ok());
+            negotiate(accept, R::FALLBACK)
+        });
+
+        format.into_response(error, parts)
+    }
+}
+
+/// Chooses the most acceptable of the provided formats for the `Accept`
+/// header value, formats earlier in the list win ties
+fn negotiate(accept: Option<&str>, formats: &[ResponseFormat]) -> ResponseFormat {
+    let default = formats.first().copied().unwrap_or(ResponseFormat::Text);
+    let Some(accept) = accept else {
+        return default;
+    };
+
+    let mut best: Option<(ResponseFormat, f32)> = None;
+    for format in formats {
+        let quality = quality(accept, format.media_type());
+        if quality > 0.0 && best.is_none_or(|(_, best)| quality > best) {
+            best = Some((*format, quality));
+        }
+    }
+
+    best.map_or(default, |(format, _)| format)
+}
+
+/// Determines the quality of the media type for the `Accept` header value
+/// using the most specific matching media range
+pub(crate) fn quality(accept: &str, media_type: &str) -> f32 {
+    let (kind, _) = media_type.split_once('/').unwrap_or((media_type, ""));
+
+    // Specificity of the best matching range and its quality
+    let mut best: Option<(u8, f32)> = None;
+    for range in accept.split(',') {
+        let mut params = range.split(';');
+        let range = params.next().unwrap_or_default().trim();
+        let quality = params
+            .find_map(|param| param.trim().strip_prefix("q="))
+            .and_then(|quality| quality.parse::<f32>().ok())
+            .unwrap_or(1.0);
+
+        let specificity = if range.eq_ignore_ascii_case(media_type) {
+            2
+        } else if range
+            .strip_suffix("/*")
+            .is_some_and(|range| range.eq_ignore_ascii_case(kind))
+        {
+            1
+        } else if range == "*/*" {
+            0
+        } else {
+            continue;
+        };
+
+        if best.is_none_or(|(best, _)| specificity > best) {
+            best = Some((specificity, quality));
+        }
+    }
+
+    best.map_or(0.0, |(_, quality)| quality)
+}
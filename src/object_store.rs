@@ -0,0 +1,55 @@
+//! Support for the errors of `object_store`
+//!
+//! Missing objects, failed preconditions and conflicts keep their meaning
+//! for the client, authentication and permission failures are treated as
+//! failures of the storage backend (502 Bad Gateway) since they are caused
+//! by the credentials of the service rather than the client. Responses use
+//! generic reasons so that paths, bucket names and credentials from the
+//! backend errors are only included in logs
+
+use http::StatusCode;
+use object_store::Error;
+
+use crate::HttpError;
+
+impl HttpError for Error {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_server_error() {
+            log::error!("{self}{}", crate::location::log_suffix());
+        } else {
+            log::debug!("{self}");
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            Error::NotFound { .. } => StatusCode::NOT_FOUND,
+            Error::InvalidPath { .. } => StatusCode::BAD_REQUEST,
+            Error::AlreadyExists { .. } => StatusCode::CONFLICT,
+            Error::Precondition { .. } => StatusCode::PRECONDITION_FAILED,
+            Error::NotModified { .. } => StatusCode::NOT_MODIFIED,
+            Error::NotSupported { .. } | Error::NotImplemented => StatusCode::NOT_IMPLEMENTED,
+            Error::Generic { .. }
+            | Error::PermissionDenied { .. }
+            | Error::Unauthenticated { .. } => StatusCode::BAD_GATEWAY,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn reason(&self) -> String {
+        match self {
+            Error::NotFound { .. } => "Object not found",
+            Error::InvalidPath { .. } => "Invalid object path",
+            Error::AlreadyExists { .. } => "Object already exists",
+            Error::Precondition { .. } => "Precondition failed",
+            Error::NotModified { .. } => "Not modified",
+            Error::NotSupported { .. } | Error::NotImplemented => "Operation not supported",
+            Error::Generic { .. }
+            | Error::PermissionDenied { .. }
+            | Error::Unauthenticated { .. } => "Storage backend error",
+            _ => "Server error",
+        }
+        .to_string()
+    }
+}
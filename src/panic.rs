@@ -0,0 +1,160 @@
+//! Layer for converting panics in handlers into [PanicError] responses
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum_core::response::{IntoResponse, Response};
+use http::Request;
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{DynHttpError, IntoHttpErrorResponse, PanicError, TextErrorResponse};
+
+/// Layer catching panics of the inner service and responding with a
+/// [PanicError] created from the panic payload
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_dyn_error::{CatchPanicLayer, TextErrorResponse};
+///
+/// async fn handler() -> &'static str {
+///     todo!()
+/// }
+///
+/// let app: Router = Router::new()
+///     .route("/", get(handler))
+///     .layer(CatchPanicLayer::<TextErrorResponse>::new());
+/// ```
+pub struct CatchPanicLayer<I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<I: IntoHttpErrorResponse> CatchPanicLayer<I> {
+    /// Creates a new layer
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: IntoHttpErrorResponse> Default for CatchPanicLayer<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: IntoHttpErrorResponse> Clone for CatchPanicLayer<I> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<S, I: IntoHttpErrorResponse> Layer<S> for CatchPanicLayer<I> {
+    type Service = CatchPanicService<S, I>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CatchPanicService {
+            inner,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Service created by [CatchPanicLayer]
+pub struct CatchPanicService<S, I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// The inner service
+    inner: S,
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<S: Clone, I: IntoHttpErrorResponse> Clone for CatchPanicService<S, I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, B, I> Service<Request<B>> for CatchPanicService<S, I>
+where
+    S: Service<Request<B>, Response = Response>,
+    I: IntoHttpErrorResponse,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = CatchPanicFuture<S::Future, I>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        // Services can also panic while creating their future
+        let inner = match catch_unwind(AssertUnwindSafe(|| self.inner.call(request))) {
+            Ok(future) => Some(future),
+            Err(payload) => {
+                return CatchPanicFuture {
+                    inner: None,
+                    response: Some(panic_response::<I>(payload)),
+                    _marker: PhantomData,
+                }
+            }
+        };
+
+        CatchPanicFuture {
+            inner,
+            response: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Creates the response for the provided panic payload
+fn panic_response<I: IntoHttpErrorResponse>(payload: Box<dyn std::any::Any + Send>) -> Response {
+    DynHttpError::<I>::from(PanicError::from_payload(payload.as_ref())).into_response()
+}
+
+pin_project! {
+    /// Future returned by [CatchPanicService]
+    pub struct CatchPanicFuture<F, I> {
+        #[pin]
+        inner: Option<F>,
+        response: Option<Response>,
+        _marker: PhantomData<fn() -> I>,
+    }
+}
+
+impl<F, E, I> Future for CatchPanicFuture<F, I>
+where
+    F: Future<Output = Result<Response, E>>,
+    I: IntoHttpErrorResponse,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut this = self.project();
+        if let Some(response) = this.response.take() {
+            return Poll::Ready(Ok(response));
+        }
+
+        let Some(inner) = this.inner.as_mut().as_pin_mut() else {
+            panic!("CatchPanicFuture polled after completion");
+        };
+
+        match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(poll) => poll,
+            Err(payload) => {
+                this.inner.set(None);
+                Poll::Ready(Ok(panic_response::<I>(payload)))
+            }
+        }
+    }
+}
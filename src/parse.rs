@@ -0,0 +1,59 @@
+//! Support for the parse errors of dates, times and IDs (`chrono`, `time`
+//! and `uuid`)
+//!
+//! The parse errors are 400 Bad Request errors, use
+//! [FieldResultExt::for_field](crate::FieldResultExt::for_field) to include
+//! the field that failed to parse in the response
+//!
+//! ```
+//! # #[cfg(feature = "uuid")]
+//! # {
+//! use axum_dyn_error::{FieldResultExt, HttpResult};
+//! use uuid::Uuid;
+//!
+//! pub async fn user(id: String) -> HttpResult<String> {
+//!     let id = Uuid::parse_str(&id).for_field("id")?;
+//!     Ok(id.to_string())
+//! }
+//! # }
+//! ```
+
+use http::StatusCode;
+
+use crate::HttpError;
+
+#[cfg(feature = "chrono")]
+impl HttpError for chrono::ParseError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+#[cfg(feature = "time")]
+impl HttpError for time::error::Parse {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl HttpError for uuid::Error {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::BAD_REQUEST
+    }
+}
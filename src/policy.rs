@@ -0,0 +1,247 @@
+//! Per-router error policies that override the global configuration
+//! for the routes they are layered on
+
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum_core::response::Response;
+use http::{request::Parts, Request, StatusCode};
+use tokio::task::futures::TaskLocalFuture;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::rewrite::RewrittenError;
+use crate::{ErrorConfig, HttpError, IntoHttpErrorResponse, Redactor};
+
+tokio::task_local! {
+    /// Policy for the request currently being handled
+    static ERROR_POLICY: Arc<ErrorPolicy>;
+}
+
+/// Function for creating a response from an error (See [IntoHttpErrorResponse::into_response_with_parts])
+type FormatterFn = fn(Box<dyn HttpError>, Option<&Parts>) -> Response;
+
+/// Function for redacting the reason of an error (See [Redactor::redact])
+type RedactorFn = fn(&dyn HttpError, &str) -> Option<String>;
+
+/// How much of the error reason is exposed in responses
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Exposure {
+    /// Reasons are included as is
+    #[default]
+    Full,
+    /// Reasons of server errors (5xx) are replaced with the canonical
+    /// reason for the status code
+    ClientErrors,
+    /// Reasons of all errors are replaced with the canonical reason
+    /// for the status code
+    None,
+}
+
+/// Error policy attached to a router using the [ErrorPolicyLayer],
+/// overriding the global [ErrorConfig] and the response formatter for
+/// the routes it is layered on (i.e different disclosure rules for
+/// internal and public APIs)
+///
+/// The policy only applies within the task handling the request, errors
+/// from work spawned onto other tasks (i.e using `tokio::spawn`) silently
+/// fall back to the global configuration (See [ErrorPolicy::current])
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_dyn_error::{ErrorPolicy, ErrorPolicyLayer, Exposure};
+///
+/// let public: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .layer(ErrorPolicyLayer::new(
+///         ErrorPolicy::new().exposure(Exposure::ClientErrors),
+///     ));
+/// ```
+#[derive(Clone, Default)]
+pub struct ErrorPolicy {
+    /// Configuration used instead of the global configuration
+    config: Option<Arc<ErrorConfig>>,
+    /// How much of the error reason is exposed
+    exposure: Exposure,
+    /// Formatter used instead of the formatter of the error type
+    formatter: Option<FormatterFn>,
+    /// Default locale for formatters that support localization
+    default_locale: Option<String>,
+    /// Redactors applied to the reasons of errors
+    redactors: Vec<RedactorFn>,
+    /// Status used for errors converted without a more specific status
+    default_status: Option<StatusCode>,
+}
+
+impl ErrorPolicy {
+    /// Creates a new policy that doesn't override anything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the configuration used instead of the global configuration
+    pub fn config(mut self, config: ErrorConfig) -> Self {
+        self.config = Some(Arc::new(config));
+        self
+    }
+
+    /// Sets how much of the error reason is exposed in responses
+    pub fn exposure(mut self, exposure: Exposure) -> Self {
+        self.exposure = exposure;
+        self
+    }
+
+    /// Sets the formatter used instead of the formatter specified
+    /// by the [DynHttpError](crate::DynHttpError) type
+    pub fn formatter<I: IntoHttpErrorResponse>(mut self) -> Self {
+        self.formatter = Some(I::into_response_with_parts);
+        self
+    }
+
+    /// Sets the default locale, provided to template error pages through
+    /// [ErrorPageContext::locale](crate::ErrorPageContext::locale) (`askama`,
+    /// `maud` and `minijinja` features) and used by the [ErrorReporter](crate::ErrorReporter)
+    /// when the request doesn't have an `Accept-Language` header. Custom
+    /// formatters can read it through [ErrorPolicy::current]
+    pub fn default_locale(mut self, locale: impl Into<String>) -> Self {
+        self.default_locale = Some(locale.into());
+        self
+    }
+
+    /// Adds a redactor that is applied to the reasons of errors, redactors
+    /// are applied in the order they are added
+    pub fn redactor<R: Redactor>(mut self) -> Self {
+        self.redactors.push(R::redact);
+        self
+    }
+
+    /// Sets the status used by the blanket conversions of errors that
+    /// don't have a more specific status, overriding [ErrorConfig::default_status]
+    /// for the routes the policy is attached to (i.e 502 Bad Gateway for a
+    /// gateway where unknown errors come from upstream clients)
+    pub fn default_status(mut self, status: StatusCode) -> Self {
+        self.default_status = Some(status);
+        self
+    }
+
+    /// The default locale of the policy
+    pub fn locale(&self) -> Option<&str> {
+        self.default_locale.as_deref()
+    }
+
+    /// Obtains the policy for the current request, this will be [None]
+    /// when called outside of an [ErrorPolicyLayer].
+    ///
+    /// The policy is stored in a task local, so it isn't available inside
+    /// work spawned onto another task (i.e `tokio::spawn`). Errors converted
+    /// or turned into responses there use the global [ErrorConfig] instead
+    /// (including its [ErrorConfig::default_status])
+    pub fn current() -> Option<Arc<ErrorPolicy>> {
+        ERROR_POLICY.try_with(Clone::clone).ok()
+    }
+
+    /// The configuration overriding the global configuration
+    pub(crate) fn config_override(&self) -> Option<Arc<ErrorConfig>> {
+        self.config.clone()
+    }
+
+    /// The status overriding the default status of the configuration
+    pub(crate) fn default_status_override(&self) -> Option<StatusCode> {
+        self.default_status
+    }
+
+    /// The formatter overriding the formatter of the error type
+    pub(crate) fn formatter_override(&self) -> Option<FormatterFn> {
+        self.formatter
+    }
+
+    /// Applies the exposure rules and redactors to the error
+    pub(crate) fn apply(&self, error: Box<dyn HttpError>) -> Box<dyn HttpError> {
+        let reason = self.redact_reason(error.as_ref());
+        let mut error = RewrittenError::new(error);
+        error.redacted = reason.is_some();
+        error.reason = reason;
+        error.into_boxed()
+    }
+
+    /// Determines the reason of the error after the exposure rules and
+    /// redactors are applied, returns [None] when the reason is unchanged
+    pub(crate) fn redact_reason(&self, error: &dyn HttpError) -> Option<String> {
+        let status = error.status();
+        let hide = match self.exposure {
+            Exposure::Full => false,
+            Exposure::ClientErrors => status.is_server_error(),
+            Exposure::None => true,
+        };
+
+        let mut reason = hide.then(|| {
+            status
+                .canonical_reason()
+                .unwrap_or("Server error")
+                .to_string()
+        });
+
+        for redactor in &self.redactors {
+            let current = reason.clone().unwrap_or_else(|| error.reason());
+            if let Some(redacted) = redactor(error, &current) {
+                reason = Some(redacted);
+            }
+        }
+
+        reason
+    }
+}
+
+/// Layer that attaches an [ErrorPolicy] to the routes it is layered on,
+/// when layers are nested the innermost policy is used
+#[derive(Clone)]
+pub struct ErrorPolicyLayer {
+    /// The policy to attach
+    policy: Arc<ErrorPolicy>,
+}
+
+impl ErrorPolicyLayer {
+    /// Creates a new layer attaching the provided policy
+    pub fn new(policy: ErrorPolicy) -> Self {
+        Self {
+            policy: Arc::new(policy),
+        }
+    }
+}
+
+impl<S> Layer<S> for ErrorPolicyLayer {
+    type Service = ErrorPolicyService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ErrorPolicyService {
+            inner,
+            policy: self.policy.clone(),
+        }
+    }
+}
+
+/// Service created by [ErrorPolicyLayer]
+#[derive(Clone)]
+pub struct ErrorPolicyService<S> {
+    /// The inner service
+    inner: S,
+    /// The attached policy
+    policy: Arc<ErrorPolicy>,
+}
+
+impl<S, B> Service<Request<B>> for ErrorPolicyService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TaskLocalFuture<Arc<ErrorPolicy>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        ERROR_POLICY.scope(self.policy.clone(), self.inner.call(request))
+    }
+}
@@ -0,0 +1,86 @@
+//! Problem details (RFC 9457 `application/problem+json`) error responses
+
+use axum_core::response::Response;
+use http::request::Parts;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::json::json_response_with_type;
+use crate::{ErrorBody, HttpError, IntoHttpErrorResponse};
+
+/// Problem details document created by [ProblemJsonResponse]
+#[derive(Debug, Serialize)]
+pub struct ProblemDetails {
+    /// URI identifying the problem type
+    #[serde(rename = "type")]
+    pub type_uri: String,
+    /// Short summary of the problem type
+    pub title: String,
+    /// The HTTP status code
+    pub status: u16,
+    /// Explanation specific to this occurrence of the problem
+    pub detail: String,
+    /// URI identifying this occurrence of the problem, the path of
+    /// the request when the request parts are available
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub instance: Option<String>,
+    /// Extension member for the machine readable error code
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code: Option<String>,
+    /// Extension member for advice on how to resolve the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub help: Option<String>,
+    /// Extension member for the steps for remediating the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+    /// Extension member for additional structured details about the error
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub details: Option<Value>,
+}
+
+impl ProblemDetails {
+    /// Creates the problem details for the provided error, the type is
+    /// `about:blank` so the title is the canonical reason for the status
+    /// (See [reason_phrase](crate::reason_phrase))
+    pub fn from_error(error: &dyn HttpError) -> Self {
+        let status = error.status();
+        Self {
+            type_uri: "about:blank".to_string(),
+            title: crate::reason_phrase(status).to_string(),
+            status: status.as_u16(),
+            detail: error.reason(),
+            instance: None,
+            code: error.code().map(str::to_string),
+            help: error.help(),
+            remediation: error.remediation(),
+            // Use the details from the error body so that they are redacted
+            details: ErrorBody::from_error(error).details,
+        }
+    }
+}
+
+/// Creates HTTP error responses with a [ProblemDetails] body using the
+/// `application/problem+json` content type
+///
+/// When the request parts are captured using the `RequestContextLayer`
+/// (Requires the `tower` feature) the `instance` member is the path of
+/// the request
+///
+/// ```
+/// use axum_dyn_error::{HttpResult, ProblemJsonResponse};
+///
+/// pub type MyHttpResult<T> = HttpResult<T, ProblemJsonResponse>;
+/// ```
+pub struct ProblemJsonResponse;
+
+impl IntoHttpErrorResponse for ProblemJsonResponse {
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        Self::into_response_with_parts(error, None)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        let mut problem = ProblemDetails::from_error(error.as_ref());
+        problem.instance = parts.map(|parts| parts.uri.path().to_string());
+        json_response_with_type(error.status(), &problem, "application/problem+json")
+    }
+}
@@ -0,0 +1,79 @@
+//! Convenience setup for exporting the error metrics to Prometheus
+
+use std::time::Duration;
+
+use axum_core::response::{IntoResponse, Response};
+use http::{header::CONTENT_TYPE, HeaderValue};
+use metrics_exporter_prometheus::{BuildError, PrometheusBuilder, PrometheusHandle};
+
+use crate::metrics::describe_metrics;
+
+/// Interval between runs of the recorder upkeep
+const UPKEEP_INTERVAL: Duration = Duration::from_secs(5);
+
+/// Installed Prometheus recorder, responds with the rendered metrics
+/// in the Prometheus text format when used as a response
+///
+/// ```no_run
+/// use axum::{routing::get, Router};
+/// use axum_dyn_error::prometheus::PrometheusMetrics;
+///
+/// let metrics = PrometheusMetrics::install().unwrap();
+/// let router: Router = Router::new().route("/metrics", get(move || async move { metrics }));
+/// ```
+#[derive(Clone)]
+pub struct PrometheusMetrics {
+    /// Handle to the installed recorder
+    handle: PrometheusHandle,
+}
+
+impl PrometheusMetrics {
+    /// Installs a Prometheus recorder as the global recorder and
+    /// describes the error metrics.
+    ///
+    /// A background thread running the recorder upkeep every 5 seconds
+    /// is spawned, without the upkeep the histogram buckets grow without
+    /// bound. The thread runs for the rest of the process
+    pub fn install() -> Result<Self, BuildError> {
+        let handle = PrometheusBuilder::new().install_recorder()?;
+
+        let upkeep = handle.clone();
+        std::thread::spawn(move || loop {
+            std::thread::sleep(UPKEEP_INTERVAL);
+            upkeep.run_upkeep();
+        });
+
+        describe_metrics();
+        Ok(Self { handle })
+    }
+
+    /// Creates from an existing handle for services that install their
+    /// own recorder, the error metrics are described. Running the upkeep
+    /// of the recorder (See [PrometheusHandle::run_upkeep]) is left to
+    /// the caller
+    pub fn from_handle(handle: PrometheusHandle) -> Self {
+        describe_metrics();
+        Self { handle }
+    }
+
+    /// Handle to the installed recorder
+    pub fn handle(&self) -> &PrometheusHandle {
+        &self.handle
+    }
+
+    /// Renders the metrics in the Prometheus text format
+    pub fn render(&self) -> String {
+        self.handle.render()
+    }
+}
+
+impl IntoResponse for PrometheusMetrics {
+    fn into_response(self) -> Response {
+        let mut response = self.render().into_response();
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("text/plain; version=0.0.4"),
+        );
+        response
+    }
+}
@@ -0,0 +1,48 @@
+//! Rendering errors into buffered `http` responses without axum, so that
+//! sidecar proxies and serverless runtimes sharing the error types can
+//! produce identical bodies
+
+use std::pin::Pin;
+use std::task::{Context, Poll, Waker};
+
+use bytes::{Bytes, BytesMut};
+use http::request::Parts;
+use http::Response;
+use http_body::Body;
+
+use crate::{DynHttpError, IntoHttpErrorResponse};
+
+/// Renders the error into a response with a buffered body using the
+/// formatter `I`, the error goes through the same logging, hooks and
+/// metrics as errors returned from handlers.
+///
+/// The request parts are used for content negotiation and by formatters
+/// that include request details, when [None] the parts captured by the
+/// `RequestContextLayer` are not used either.
+///
+/// Response bodies created by the formatters are always buffered, bodies
+/// of errors that stream their response (See [HttpError::into_response_override](crate::HttpError::into_response_override))
+/// only include the data that is immediately available
+///
+/// ```
+/// use axum_dyn_error::{render_error, DynHttpError, TextErrorResponse};
+///
+/// let error: DynHttpError = DynHttpError::not_found("Unknown route");
+/// let response = render_error::<TextErrorResponse>(error, None);
+/// assert_eq!(response.status(), 404);
+/// assert_eq!(response.body().as_ref(), b"Unknown route");
+/// ```
+pub fn render_error<I: IntoHttpErrorResponse>(
+    error: DynHttpError<I>,
+    parts: Option<&Parts>,
+) -> Response<Bytes> {
+    let (head, mut body) = error.into_response_with_parts(parts).into_parts();
+
+    let mut buffer = BytesMut::new();
+    let mut cx = Context::from_waker(Waker::noop());
+    while let Poll::Ready(Some(Ok(chunk))) = Pin::new(&mut body).poll_data(&mut cx) {
+        buffer.extend_from_slice(&chunk);
+    }
+
+    Response::from_parts(head, buffer.freeze())
+}
@@ -0,0 +1,159 @@
+//! Line oriented error report formatters for batch and import endpoints
+//! that render [MultiHttpError]s as CSV or NDJSON so that the report can
+//! be opened directly by spreadsheet users or processed line by line
+
+use std::marker::PhantomData;
+
+use axum_core::response::{IntoResponse, Response};
+use http::{header::CONTENT_TYPE, request::Parts, HeaderValue};
+
+use crate::rewrite::RewrittenError;
+use crate::{
+    ErrorConfig, ErrorEntry, HttpError, IntoHttpErrorResponse, MultiHttpError, TextErrorResponse,
+};
+
+/// Formatter that renders [MultiHttpError]s as a CSV report with the
+/// columns `line,field,code,message`, other errors are passed to the
+/// inner formatter `I`. Fields starting with a formula character (`=`,
+/// `+`, `-`, `@`, tab or carriage return) are prefixed with `'`.
+///
+/// Messages are truncated like reasons (See [ErrorConfig::max_reason_length])
+/// and errors that were masked or had their reason redacted are passed to
+/// the inner formatter instead of being reported
+pub struct CsvErrorReport<I = TextErrorResponse> {
+    /// Marker for storing the formatter type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<I: IntoHttpErrorResponse> IntoHttpErrorResponse for CsvErrorReport<I> {
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        Self::into_response_with_parts(error, None)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        let Some(multi) = report_source(error.as_ref()) else {
+            return I::into_response_with_parts(error, parts);
+        };
+
+        let config = crate::config::current_config();
+        let mut body = String::from("line,field,code,message\n");
+        for entry in multi
+            .entries()
+            .iter()
+            .map(|entry| report_entry(entry, &config))
+        {
+            if let Some(line) = entry.line {
+                body.push_str(&line.to_string());
+            }
+            body.push(',');
+            push_csv_field(&mut body, entry.field.as_deref().unwrap_or_default());
+            body.push(',');
+            push_csv_field(&mut body, entry.code.as_deref().unwrap_or_default());
+            body.push(',');
+            push_csv_field(&mut body, &entry.message);
+            body.push('\n');
+        }
+
+        let mut response = (error.status(), body).into_response();
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("text/csv; charset=utf-8"),
+        );
+        response
+    }
+}
+
+/// Finds the [MultiHttpError] to report on, errors that were masked or
+/// redacted aren't reported as their entries would expose the original error
+fn report_source(error: &dyn HttpError) -> Option<&MultiHttpError> {
+    if RewrittenError::is_hidden(error) {
+        return None;
+    }
+
+    error.downcast_ref::<MultiHttpError>()
+}
+
+/// Creates the entry included in a report, messages are truncated to the
+/// maximum reason length (See [ErrorConfig::max_reason_length]) and with
+/// the `secrecy` feature the messages of entries for sensitive fields are
+/// redacted (See `is_sensitive_key`)
+fn report_entry(entry: &ErrorEntry, config: &ErrorConfig) -> ErrorEntry {
+    let mut entry = entry.clone();
+    if let Some(truncated) = config.truncate(&entry.message) {
+        entry.message = truncated;
+    }
+
+    #[cfg(feature = "secrecy")]
+    if entry
+        .field
+        .as_deref()
+        .is_some_and(crate::secret::is_sensitive_key)
+    {
+        entry.message = crate::secret::REDACTED.to_string();
+    }
+
+    entry
+}
+
+/// Appends a CSV field quoting it if required, fields that spreadsheets
+/// would evaluate as formulas are prefixed with `'` so that messages
+/// containing user input can't inject formulas into the report
+fn push_csv_field(output: &mut String, value: &str) {
+    let formula = value.starts_with(['=', '+', '-', '@', '\t', '\r']);
+    if value.contains([',', '"', '\n', '\r']) {
+        output.push('"');
+        if formula {
+            output.push('\'');
+        }
+        output.push_str(&value.replace('"', "\"\""));
+        output.push('"');
+    } else {
+        if formula {
+            output.push('\'');
+        }
+        output.push_str(value);
+    }
+}
+
+/// Formatter that renders [MultiHttpError]s as newline delimited JSON
+/// with one entry per line, other errors are passed to the inner
+/// formatter `I`. Entries are truncated and masked or redacted
+/// errors are not reported, the same as [CsvErrorReport]
+#[cfg(feature = "json")]
+pub struct NdjsonErrorReport<I = TextErrorResponse> {
+    /// Marker for storing the formatter type
+    _marker: PhantomData<fn() -> I>,
+}
+
+#[cfg(feature = "json")]
+impl<I: IntoHttpErrorResponse> IntoHttpErrorResponse for NdjsonErrorReport<I> {
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        Self::into_response_with_parts(error, None)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        let Some(multi) = report_source(error.as_ref()) else {
+            return I::into_response_with_parts(error, parts);
+        };
+
+        let config = crate::config::current_config();
+        let mut body = Vec::new();
+        for entry in multi
+            .entries()
+            .iter()
+            .map(|entry| report_entry(entry, &config))
+        {
+            if serde_json::to_writer(&mut body, &entry).is_err() {
+                return http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+            }
+            body.push(b'\n');
+        }
+
+        let mut response = (error.status(), body).into_response();
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("application/x-ndjson"),
+        );
+        response
+    }
+}
@@ -0,0 +1,166 @@
+//! Extractor providing handlers with a factory for creating errors that
+//! are tagged with details about the current request
+
+use std::convert::Infallible;
+use std::fmt::Display;
+use std::marker::PhantomData;
+
+use async_trait::async_trait;
+use axum_core::extract::FromRequestParts;
+use http::{header::ACCEPT_LANGUAGE, request::Parts};
+
+use crate::{
+    DynHttpError, ErrorPolicy, IntoHttpErrorResponse, TextErrorResponse, REQUEST_ID_HEADER,
+};
+
+/// Extractor for creating errors tagged with the ID and locale of the
+/// current request, with the `json` feature the tags are attached to
+/// the error details as `request_id` and `locale`
+///
+/// The locale is taken from the `Accept-Language` header falling back
+/// to the default locale of the current [ErrorPolicy]
+///
+/// ```
+/// use axum_dyn_error::{ErrorReporter, HttpResult};
+///
+/// pub async fn get_user(reporter: ErrorReporter) -> HttpResult<String> {
+///     Err(reporter.not_found("User"))
+/// }
+/// ```
+pub struct ErrorReporter<I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// The ID of the request
+    request_id: Option<String>,
+    /// The preferred locale of the request
+    locale: Option<String>,
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<I: IntoHttpErrorResponse> ErrorReporter<I> {
+    /// Creates a reporter from the parts of a request
+    pub fn from_parts(parts: &Parts) -> Self {
+        let request_id = parts
+            .headers
+            .get(REQUEST_ID_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .map(str::to_string);
+
+        let locale = parts
+            .headers
+            .get(ACCEPT_LANGUAGE)
+            .and_then(|value| value.to_str().ok())
+            .and_then(preferred_locale)
+            .or_else(|| {
+                ErrorPolicy::current().and_then(|policy| policy.locale().map(str::to_string))
+            });
+
+        Self {
+            request_id,
+            locale,
+            _marker: PhantomData,
+        }
+    }
+
+    /// The ID of the request from the `x-request-id` header
+    pub fn request_id(&self) -> Option<&str> {
+        self.request_id.as_deref()
+    }
+
+    /// The preferred locale of the request
+    pub fn locale(&self) -> Option<&str> {
+        self.locale.as_deref()
+    }
+
+    /// Tags the provided error with the details of the request
+    pub fn capture(&self, error: impl Into<DynHttpError<I>>) -> DynHttpError<I> {
+        let error = error.into();
+
+        #[cfg(feature = "json")]
+        let error = {
+            let mut error = error;
+            if let Some(request_id) = &self.request_id {
+                error = error.attach("request_id", request_id);
+            }
+            if let Some(locale) = &self.locale {
+                error = error.attach("locale", locale);
+            }
+            error
+        };
+
+        error
+    }
+
+    /// Creates a tagged 400 Bad Request error
+    pub fn bad_request(&self, message: impl Into<String>) -> DynHttpError<I> {
+        self.capture(DynHttpError::bad_request(message))
+    }
+
+    /// Creates a tagged 401 Unauthorized error
+    pub fn unauthorized(&self, message: impl Into<String>) -> DynHttpError<I> {
+        self.capture(DynHttpError::unauthorized(message))
+    }
+
+    /// Creates a tagged 403 Forbidden error
+    pub fn forbidden(&self, message: impl Into<String>) -> DynHttpError<I> {
+        self.capture(DynHttpError::forbidden(message))
+    }
+
+    /// Creates a tagged 404 Not Found error for the named resource
+    /// (i.e `reporter.not_found("User")` is "User not found")
+    pub fn not_found(&self, resource: impl Display) -> DynHttpError<I> {
+        self.capture(DynHttpError::not_found(format!("{resource} not found")))
+    }
+
+    /// Creates a tagged 409 Conflict error
+    pub fn conflict(&self, message: impl Into<String>) -> DynHttpError<I> {
+        self.capture(DynHttpError::conflict(message))
+    }
+
+    /// Creates a tagged 500 Internal Server Error, the message is only
+    /// included in logs
+    pub fn internal(&self, message: impl Into<String>) -> DynHttpError<I> {
+        self.capture(DynHttpError::internal(message))
+    }
+}
+
+impl<I: IntoHttpErrorResponse> Clone for ErrorReporter<I> {
+    fn clone(&self) -> Self {
+        Self {
+            request_id: self.request_id.clone(),
+            locale: self.locale.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+#[async_trait]
+impl<S, I> FromRequestParts<S> for ErrorReporter<I>
+where
+    S: Send + Sync,
+    I: IntoHttpErrorResponse,
+{
+    type Rejection = Infallible;
+
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        Ok(Self::from_parts(parts))
+    }
+}
+
+/// Determines the most preferred locale from an `Accept-Language` header value
+fn preferred_locale(value: &str) -> Option<String> {
+    // Reversed so that the first of the equally preferred locales is used
+    value
+        .split(',')
+        .rev()
+        .filter_map(|entry| {
+            let mut parts = entry.split(';');
+            let tag = parts.next()?.trim();
+            let quality = parts
+                .find_map(|param| param.trim().strip_prefix("q="))
+                .and_then(|quality| quality.parse::<f32>().ok())
+                .unwrap_or(1.0);
+            (!tag.is_empty() && tag != "*" && quality > 0.0).then_some((tag, quality))
+        })
+        .max_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(tag, _)| tag.to_string())
+}
@@ -0,0 +1,30 @@
+//! Internal representation of the error stored by [DynHttpError](crate::DynHttpError)
+
+use crate::{HttpError, StaticHttpError};
+
+/// Representation of the error stored by a [DynHttpError](crate::DynHttpError),
+/// static errors are stored inline so that creating them doesn't allocate
+pub(crate) enum ErrorRepr {
+    /// Dynamically typed boxed error
+    Boxed(Box<dyn HttpError>),
+    /// Error created from a static message
+    Static(StaticHttpError),
+}
+
+impl ErrorRepr {
+    /// Provides access to the error as an [HttpError]
+    pub(crate) fn as_error(&self) -> &dyn HttpError {
+        match self {
+            ErrorRepr::Boxed(error) => error.as_ref(),
+            ErrorRepr::Static(error) => error,
+        }
+    }
+
+    /// Converts the representation into a boxed error
+    pub(crate) fn into_boxed(self) -> Box<dyn HttpError> {
+        match self {
+            ErrorRepr::Boxed(error) => error,
+            ErrorRepr::Static(error) => Box::new(error),
+        }
+    }
+}
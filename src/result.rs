@@ -0,0 +1,131 @@
+//! Extension trait for enriching errors within [HttpResult]s
+
+use http::StatusCode;
+
+use crate::{ErrorEntry, HttpResult, IntoHttpErrorResponse, MultiHttpError};
+
+/// Extension trait for enriching the error of an [HttpResult] as it
+/// bubbles up through service layers
+///
+/// ```
+/// use axum_dyn_error::{DynHttpError, HttpResult, HttpResultExt, StatusCode};
+///
+/// fn find_user() -> HttpResult<()> {
+///     Err(DynHttpError::not_found("User not found"))
+/// }
+///
+/// fn find_owner() -> HttpResult<()> {
+///     find_user()
+///         .map_status(StatusCode::CONFLICT)
+///         .with_code("OWNER_MISSING")
+/// }
+/// ```
+pub trait HttpResultExt<T, I: IntoHttpErrorResponse> {
+    /// Logs the error (if any) using [HttpError::log](crate::HttpError::log)
+    /// without consuming it, the error will be logged again when its
+    /// converted into a response
+    #[cfg(feature = "log")]
+    fn tap_err_log(self) -> Self;
+
+    /// Replaces the status code of the error (See [DynHttpError::map_status](crate::DynHttpError::map_status))
+    fn map_status(self, status: StatusCode) -> Self;
+
+    /// Replaces the machine readable code of the error (See [DynHttpError::with_code](crate::DynHttpError::with_code))
+    fn with_code(self, code: impl Into<String>) -> Self;
+
+    /// Attaches a key-value pair to the error details (See [DynHttpError::attach](crate::DynHttpError::attach))
+    #[cfg(feature = "json")]
+    fn attach(self, key: impl Into<String>, value: impl serde::Serialize) -> Self;
+}
+
+impl<T, I: IntoHttpErrorResponse> HttpResultExt<T, I> for HttpResult<T, I> {
+    #[cfg(feature = "log")]
+    fn tap_err_log(self) -> Self {
+        if let Err(error) = &self {
+            #[cfg(feature = "track-caller")]
+            crate::location::with_location(error.location, || error.inner.as_error().log());
+            #[cfg(not(feature = "track-caller"))]
+            error.inner.as_error().log();
+        }
+        self
+    }
+
+    fn map_status(self, status: StatusCode) -> Self {
+        self.map_err(|error| error.map_status(status))
+    }
+
+    fn with_code(self, code: impl Into<String>) -> Self {
+        self.map_err(|error| error.with_code(code))
+    }
+
+    #[cfg(feature = "json")]
+    fn attach(self, key: impl Into<String>, value: impl serde::Serialize) -> Self {
+        self.map_err(|error| error.attach(key, value))
+    }
+}
+
+/// Extension trait for iterators of [HttpResult]s
+pub trait HttpResultIterExt<T, I: IntoHttpErrorResponse>:
+    Iterator<Item = HttpResult<T, I>> + Sized
+{
+    /// Splits the results into the successful values and a [MultiHttpError]
+    /// containing an [ErrorEntry] for each failure (with the index of the
+    /// failed item), for batch processing that continues past individual
+    /// failures and reports them all. Only the reason and code of each
+    /// failure are kept so the failures are logged as they are collected
+    ///
+    /// ```
+    /// use axum_dyn_error::{DynHttpError, HttpResult, HttpResultIterExt};
+    ///
+    /// fn import(rows: &[&str]) -> HttpResult<Vec<u32>> {
+    ///     let (ids, errors) = rows
+    ///         .iter()
+    ///         .map(|row| -> HttpResult<u32> {
+    ///             row.parse()
+    ///                 .map_err(|_| DynHttpError::bad_request("Invalid ID"))
+    ///         })
+    ///         .partition_http_results();
+    ///
+    ///     if !errors.is_empty() {
+    ///         return Err(errors.into());
+    ///     }
+    ///
+    ///     Ok(ids)
+    /// }
+    ///
+    /// assert!(import(&["1", "two", "3"]).is_err());
+    /// ```
+    fn partition_http_results(self) -> (Vec<T>, MultiHttpError) {
+        let mut values = Vec::new();
+        let mut errors = MultiHttpError::new();
+
+        for (index, result) in self.enumerate() {
+            match result {
+                Ok(value) => values.push(value),
+                Err(error) => {
+                    #[cfg(feature = "log")]
+                    {
+                        #[cfg(feature = "track-caller")]
+                        crate::location::with_location(error.location, || {
+                            error.inner.as_error().log()
+                        });
+                        #[cfg(not(feature = "track-caller"))]
+                        error.inner.as_error().log();
+                    }
+
+                    let entry = ErrorEntry::from_error(error.inner.as_error()).index(index as u64);
+                    errors.push(entry);
+                }
+            }
+        }
+
+        (values, errors)
+    }
+}
+
+impl<T, I, It> HttpResultIterExt<T, I> for It
+where
+    I: IntoHttpErrorResponse,
+    It: Iterator<Item = HttpResult<T, I>>,
+{
+}
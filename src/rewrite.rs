@@ -0,0 +1,325 @@
+//! Wrapper used to override parts of an error before it reaches
+//! the response formatter
+
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+use http::header::{SET_COOKIE, WWW_AUTHENTICATE};
+use http::{Extensions, HeaderMap, HeaderName, StatusCode};
+
+use crate::{ConnectionDirective, ErrorKind, GrpcCode, HttpError};
+
+/// Wrapper around an [HttpError] that replaces parts of the inner
+/// error while forwarding everything else
+pub(crate) struct RewrittenError {
+    /// The original error
+    inner: Box<dyn HttpError>,
+    /// Replacement status code
+    pub(crate) status: Option<StatusCode>,
+    /// Replacement reason
+    pub(crate) reason: Option<String>,
+    /// Replacement error code
+    pub(crate) code: Option<String>,
+    /// Context describing what was happening when the error occurred,
+    /// prefixed to the message of the inner error
+    pub(crate) context: Option<String>,
+    /// Additional headers added after the inner error headers
+    pub(crate) headers: Option<HeaderMap>,
+    /// Additional fields merged into the inner error details
+    #[cfg(feature = "json")]
+    pub(crate) attachments: Option<serde_json::Map<String, serde_json::Value>>,
+    /// Replacement details, used instead of the inner details and attachments
+    #[cfg(feature = "json")]
+    pub(crate) details: Option<serde_json::Value>,
+    /// Whether the fields of the inner error that are exposed to clients
+    /// (code, details, help, remediation and headers) are hidden
+    pub(crate) masked: bool,
+    /// Whether the reason was replaced by a redactor or exposure rule
+    pub(crate) redacted: bool,
+}
+
+impl RewrittenError {
+    /// Wraps the provided error without any replacements
+    pub(crate) fn new(inner: Box<dyn HttpError>) -> Self {
+        Self {
+            inner,
+            status: None,
+            reason: None,
+            code: None,
+            context: None,
+            headers: None,
+            #[cfg(feature = "json")]
+            attachments: None,
+            #[cfg(feature = "json")]
+            details: None,
+            masked: false,
+            redacted: false,
+        }
+    }
+
+    /// Hides everything exposed to clients apart from the status and
+    /// reason, so that the response is identical to one for an error
+    /// that only has the status and reason. Headers that manage the
+    /// client session are kept (See [is_masked_header_kept])
+    #[cfg(feature = "axum")]
+    pub(crate) fn mask(&mut self) {
+        self.masked = true;
+        self.code = None;
+        #[cfg(feature = "json")]
+        {
+            self.attachments = None;
+            self.details = None;
+        }
+    }
+
+    /// Checks whether the error or any error it wraps was masked or had
+    /// its reason redacted, so content derived from the original error
+    /// must not be exposed
+    #[cfg(feature = "axum")]
+    pub(crate) fn is_hidden(error: &dyn HttpError) -> bool {
+        let mut error: &(dyn Error + 'static) = error;
+        while let Some(rewritten) = error.downcast_ref::<RewrittenError>() {
+            if rewritten.masked || rewritten.redacted {
+                return true;
+            }
+            error = rewritten.inner();
+        }
+        false
+    }
+
+    /// Provides access to the original error
+    pub(crate) fn inner(&self) -> &dyn HttpError {
+        self.inner.as_ref()
+    }
+
+    /// Unwraps the rewritten error, only wrapping the inner error
+    /// if any replacements were made
+    pub(crate) fn into_boxed(self) -> Box<dyn HttpError> {
+        if self.status.is_none()
+            && self.reason.is_none()
+            && self.code.is_none()
+            && self.context.is_none()
+            && self.headers.is_none()
+            && self.has_no_attachments()
+            && !self.masked
+            && !self.redacted
+        {
+            return self.inner;
+        }
+
+        Box::new(self)
+    }
+
+    /// Checks that no attachments were added to the details and the
+    /// details weren't replaced
+    fn has_no_attachments(&self) -> bool {
+        #[cfg(feature = "json")]
+        {
+            self.attachments.is_none() && self.details.is_none()
+        }
+
+        #[cfg(not(feature = "json"))]
+        {
+            true
+        }
+    }
+}
+
+/// Checks whether a header is kept when an error is masked, cookies and
+/// site data are kept so that clearing an expired or invalid session still
+/// works and 401 responses keep the challenges they require. Every other
+/// header could reveal which error was masked
+fn is_masked_header_kept(name: &HeaderName, status: StatusCode) -> bool {
+    name == SET_COOKIE
+        || name == "clear-site-data"
+        || (name == WWW_AUTHENTICATE && status == StatusCode::UNAUTHORIZED)
+}
+
+impl Debug for RewrittenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Debug::fmt(&self.inner, f)
+    }
+}
+
+impl Display for RewrittenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if let Some(context) = &self.context {
+            write!(f, "{context}: ")?;
+        }
+        Display::fmt(&self.inner, f)
+    }
+}
+
+impl Error for RewrittenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.inner.source()
+    }
+
+    #[cfg(feature = "provide")]
+    fn provide<'a>(&'a self, request: &mut std::error::Request<'a>) {
+        self.inner.provide(request)
+    }
+}
+
+impl HttpError for RewrittenError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.context.is_none() {
+            return self.inner.log();
+        }
+
+        // The inner error can't include the context in its own log
+        crate::log_default(self)
+    }
+
+    fn status(&self) -> StatusCode {
+        self.status.unwrap_or_else(|| self.inner.status())
+    }
+
+    fn reason(&self) -> String {
+        match &self.reason {
+            Some(reason) => reason.clone(),
+            None => self.inner.reason(),
+        }
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        if !self.masked {
+            self.inner.headers(headers);
+            if let Some(extra) = &self.headers {
+                headers.extend(extra.clone());
+            }
+            return;
+        }
+
+        let mut all = HeaderMap::new();
+        self.inner.headers(&mut all);
+        if let Some(extra) = &self.headers {
+            all.extend(extra.clone());
+        }
+
+        let status = self.status();
+        let mut name = None;
+        for (next, value) in all {
+            // Later values of the same header don't repeat the name
+            name = next.or(name);
+            if let Some(name) = name
+                .as_ref()
+                .filter(|name| is_masked_header_kept(name, status))
+            {
+                headers.append(name.clone(), value);
+            }
+        }
+    }
+
+    fn extensions(&self, extensions: &mut Extensions) {
+        self.inner.extensions(extensions)
+    }
+
+    fn code(&self) -> Option<&str> {
+        match &self.code {
+            Some(code) => Some(code),
+            None if self.masked => None,
+            None => self.inner.code(),
+        }
+    }
+
+    fn help(&self) -> Option<String> {
+        if self.masked {
+            return None;
+        }
+        self.inner.help()
+    }
+
+    fn remediation(&self) -> Option<String> {
+        if self.masked {
+            return None;
+        }
+        self.inner.remediation()
+    }
+
+    fn kind(&self) -> ErrorKind {
+        // Rewritten statuses shouldn't reveal the kind of the original error
+        match self.status {
+            Some(status) => ErrorKind::from_status(status),
+            None => self.inner.kind(),
+        }
+    }
+
+    fn grpc_code(&self) -> GrpcCode {
+        match self.status {
+            Some(status) => GrpcCode::from_status(status),
+            None => self.inner.grpc_code(),
+        }
+    }
+
+    fn connection_directive(&self) -> ConnectionDirective {
+        self.inner.connection_directive()
+    }
+
+    fn security_relevant(&self) -> bool {
+        match self.status {
+            Some(status) => crate::status::is_security_relevant_status(status),
+            None => self.inner.security_relevant(),
+        }
+    }
+
+    fn is_transient(&self) -> bool {
+        match self.status {
+            Some(status) => crate::status::is_transient_status(status),
+            None => self.inner.is_transient(),
+        }
+    }
+
+    fn bodiless(&self) -> bool {
+        match self.status {
+            Some(status) => crate::status::is_bodiless_status(status),
+            None => self.inner.bodiless(),
+        }
+    }
+
+    #[cfg(feature = "axum")]
+    fn into_response_override(&self) -> Option<axum_core::response::Response> {
+        if self.masked {
+            return None;
+        }
+        self.inner.into_response_override()
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        use serde_json::Value;
+
+        if self.masked {
+            return None;
+        }
+
+        if let Some(details) = &self.details {
+            return Some(details.clone());
+        }
+
+        let Some(attachments) = &self.attachments else {
+            return self.inner.details();
+        };
+
+        let mut details = attachments.clone();
+        match self.inner.details() {
+            // Attachments are merged into object details
+            Some(Value::Object(inner)) => {
+                for (key, value) in inner {
+                    details.entry(key).or_insert(value);
+                }
+            }
+            Some(inner) => {
+                details.insert("details".to_string(), inner);
+            }
+            None => {}
+        }
+
+        Some(Value::Object(details))
+    }
+
+    fn type_name(&self) -> &str {
+        self.inner.type_name()
+    }
+}
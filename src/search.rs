@@ -0,0 +1,121 @@
+//! Support for the errors of search backends (`elasticsearch` and
+//! `meilisearch-sdk`)
+//!
+//! Missing indexes are 404 Not Found errors, invalid queries are 400 Bad
+//! Request errors, timeouts are 504 Gateway Timeout errors and unreachable
+//! clusters are 503 Service Unavailable errors. Responses use a generic
+//! reason and the error reported by the backend is only included in logs
+
+use http::StatusCode;
+
+use crate::HttpError;
+
+/// Maps the status of a response from the search backend into the status
+/// of the error
+fn backend_status(status: u16) -> StatusCode {
+    match status {
+        404 => StatusCode::NOT_FOUND,
+        400 => StatusCode::BAD_REQUEST,
+        408 | 504 => StatusCode::GATEWAY_TIMEOUT,
+        502 | 503 => StatusCode::SERVICE_UNAVAILABLE,
+        _ => StatusCode::BAD_GATEWAY,
+    }
+}
+
+/// Creates the generic reason for a search backend error
+fn search_reason(status: StatusCode) -> String {
+    match status {
+        StatusCode::NOT_FOUND => "Search index not found",
+        StatusCode::BAD_REQUEST => "Invalid search query",
+        StatusCode::GATEWAY_TIMEOUT => "Search timed out",
+        StatusCode::SERVICE_UNAVAILABLE => "Search unavailable",
+        _ => "Server error",
+    }
+    .to_string()
+}
+
+/// Errors created from responses using `error_for_status_code` take the
+/// status of the response, timeouts and failed connections to the cluster
+/// are 504 and 503 errors, other errors are server errors
+#[cfg(feature = "elasticsearch")]
+impl HttpError for elasticsearch::Error {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_client_error() {
+            log::debug!("{self}");
+        } else {
+            log::error!("{self}{}", crate::location::log_suffix());
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        use elasticsearch::http::transport::BuildError;
+        use std::error::Error;
+
+        if self.is_timeout() {
+            return StatusCode::GATEWAY_TIMEOUT;
+        }
+
+        if let Some(status) = self.status_code() {
+            return backend_status(status.as_u16());
+        }
+
+        // Transport errors without a response are failures reaching the cluster
+        let unreachable = !self.is_json()
+            && self
+                .source()
+                .is_some_and(|source| !source.is::<BuildError>());
+
+        if unreachable {
+            StatusCode::SERVICE_UNAVAILABLE
+        } else {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+
+    fn reason(&self) -> String {
+        search_reason(self.status())
+    }
+}
+
+/// Errors reported by Meilisearch take their status from the error code
+/// and type, tasks that don't complete in time are 504 errors and failed
+/// requests to the instance are 503 errors, other errors are server errors
+#[cfg(feature = "meilisearch")]
+impl HttpError for meilisearch_sdk::errors::Error {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_client_error() {
+            log::debug!("{self}");
+        } else {
+            log::error!("{self}{}", crate::location::log_suffix());
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        use meilisearch_sdk::errors::{Error, ErrorCode, ErrorType};
+
+        match self {
+            Error::Meilisearch(error) => match (&error.error_code, &error.error_type) {
+                (ErrorCode::IndexNotFound | ErrorCode::DocumentNotFound, _) => {
+                    StatusCode::NOT_FOUND
+                }
+                (_, ErrorType::InvalidRequest) => StatusCode::BAD_REQUEST,
+                _ => StatusCode::BAD_GATEWAY,
+            },
+            Error::MeilisearchCommunication(error) => backend_status(error.status_code),
+            Error::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            Error::HttpError(error) if error.is_timeout() => StatusCode::GATEWAY_TIMEOUT,
+            Error::HttpError(error) if error.is_connect() => StatusCode::SERVICE_UNAVAILABLE,
+            Error::HttpError(error) => match error.status() {
+                Some(status) => backend_status(status.as_u16()),
+                None => StatusCode::BAD_GATEWAY,
+            },
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn reason(&self) -> String {
+        search_reason(self.status())
+    }
+}
@@ -0,0 +1,163 @@
+//! Helpers for keeping [secrecy] secrets out of error messages,
+//! details and logs
+
+use std::fmt::{Debug, Display};
+
+use secrecy::{ExposeSecret, Secret, Zeroize};
+
+/// Replacement text used for redacted values
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Keys of detail fields that are always redacted. Keys are split into
+/// lowercase segments on `_`, `-`, `.` and camel case boundaries and match
+/// when they end with the segments of a sensitive key (i.e `access_token`
+/// and `stripeApiKey` match but `token_count` and `author` don't).
+///
+/// This heuristic only looks at key names, [Secret] values serialized
+/// into the details under other keys are not detected so secrets should
+/// be included using [SafeDisplay] instead
+pub const SENSITIVE_KEYS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "authorization",
+    "cookie",
+    "api_key",
+    "apikey",
+    "private_key",
+];
+
+/// Checks whether the key of a detail field or the field of an error
+/// entry is one of the [SENSITIVE_KEYS]
+///
+/// ```
+/// use axum_dyn_error::is_sensitive_key;
+///
+/// assert!(is_sensitive_key("access_token"));
+/// assert!(is_sensitive_key("stripeApiKey"));
+/// assert!(is_sensitive_key("X-Auth-Token"));
+/// assert!(!is_sensitive_key("token_count"));
+/// assert!(!is_sensitive_key("author"));
+/// ```
+pub fn is_sensitive_key(key: &str) -> bool {
+    let segments = key_segments(key);
+    SENSITIVE_KEYS.iter().any(|sensitive| {
+        let sensitive: Vec<&str> = sensitive.split('_').collect();
+        segments.len() >= sensitive.len()
+            && segments[segments.len() - sensitive.len()..]
+                .iter()
+                .zip(&sensitive)
+                .all(|(segment, sensitive)| segment == sensitive)
+    })
+}
+
+/// Splits a key into lowercase segments on separators and camel case boundaries
+fn key_segments(key: &str) -> Vec<String> {
+    let mut segments = Vec::new();
+    let mut current = String::new();
+    let mut previous_lower = false;
+
+    for char in key.chars() {
+        if matches!(char, '_' | '-' | '.' | ' ') {
+            segments.extend((!current.is_empty()).then(|| std::mem::take(&mut current)));
+            previous_lower = false;
+            continue;
+        }
+
+        if char.is_uppercase() && previous_lower {
+            segments.push(std::mem::take(&mut current));
+        }
+
+        previous_lower = char.is_lowercase() || char.is_ascii_digit();
+        current.extend(char.to_lowercase());
+    }
+
+    segments.extend((!current.is_empty()).then_some(current));
+    segments
+}
+
+/// Safe representation of a [Secret] for use when implementing [Display]
+/// for errors or creating error details, the secret is formatted (and
+/// serialized with the `serde` feature) as [REDACTED]. The length of the
+/// secret can be included to help debugging (i.e spotting empty or
+/// truncated tokens) using [SafeDisplay::with_length]
+///
+/// ```
+/// use axum_dyn_error::SafeDisplay;
+/// use secrecy::SecretString;
+///
+/// let token = SecretString::new("hunter2".to_string());
+/// assert_eq!(SafeDisplay::new(&token).to_string(), "[REDACTED]");
+/// assert_eq!(SafeDisplay::new(&token).with_length().to_string(), "[REDACTED 7 bytes]");
+/// ```
+pub struct SafeDisplay<'a, T: Zeroize> {
+    /// The wrapped secret
+    secret: &'a Secret<T>,
+    /// Length of the secret in bytes when it should be included
+    length: Option<usize>,
+}
+
+impl<'a, T: Zeroize> SafeDisplay<'a, T> {
+    /// Creates a safe representation of the provided secret
+    pub fn new(secret: &'a Secret<T>) -> Self {
+        Self {
+            secret,
+            length: None,
+        }
+    }
+
+    /// Includes the length in bytes of the secret
+    pub fn with_length(mut self) -> Self
+    where
+        T: AsRef<[u8]>,
+    {
+        self.length = Some(self.secret.expose_secret().as_ref().len());
+        self
+    }
+}
+
+impl<T: Zeroize> Display for SafeDisplay<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.length {
+            Some(length) => write!(f, "[REDACTED {length} bytes]"),
+            None => f.write_str(REDACTED),
+        }
+    }
+}
+
+impl<T: Zeroize> Debug for SafeDisplay<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        Display::fmt(self, f)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: Zeroize> serde::Serialize for SafeDisplay<'_, T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// Redacts the values of any fields within the details whose key matches
+/// one of the [SENSITIVE_KEYS] (See [is_sensitive_key]), this is applied to
+/// the details of every error by the formatters, error reports and
+/// [ErrorEvent](crate::ErrorEvent)s when the `secrecy` feature is enabled
+#[cfg(feature = "json")]
+pub fn redact_details(value: &mut serde_json::Value) {
+    use serde_json::Value;
+
+    match value {
+        Value::Object(fields) => {
+            for (key, value) in fields.iter_mut() {
+                if is_sensitive_key(key) {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_details(value);
+                }
+            }
+        }
+        Value::Array(values) => values.iter_mut().for_each(redact_details),
+        _ => {}
+    }
+}
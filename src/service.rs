@@ -0,0 +1,185 @@
+//! Service wrapper converting the errors of any service into responses,
+//! for using the error pipeline outside of axum handlers (i.e raw hyper
+//! or tower stacks)
+
+use std::convert::Infallible;
+use std::future::Future;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use axum_core::response::{IntoResponse, Response};
+use http::Request;
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{DynHttpError, IntoHttpErrorResponse, TextErrorResponse};
+
+/// Layer wrapping services with a [DynErrorService]
+pub struct DynErrorLayer<I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<I: IntoHttpErrorResponse> DynErrorLayer<I> {
+    /// Creates a new layer
+    pub fn new() -> Self {
+        Self {
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: IntoHttpErrorResponse> Default for DynErrorLayer<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: IntoHttpErrorResponse> Clone for DynErrorLayer<I> {
+    fn clone(&self) -> Self {
+        Self::new()
+    }
+}
+
+impl<S, I: IntoHttpErrorResponse> Layer<S> for DynErrorLayer<I> {
+    type Service = DynErrorService<S, I>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DynErrorService::new(inner)
+    }
+}
+
+/// Service wrapping a service whose errors can be converted into a
+/// [DynHttpError], errors are converted into responses using the
+/// formatter `I` and go through the same logging, hooks and metrics as
+/// errors returned from handlers. The resulting service never fails
+///
+/// ```
+/// use axum::{body::Body, response::Response};
+/// use axum_dyn_error::{DynErrorService, DynHttpError, TextErrorResponse};
+/// use http::Request;
+/// use tower::{service_fn, ServiceExt};
+///
+/// async fn handle(request: Request<Body>) -> Result<Response, DynHttpError> {
+///     Err(DynHttpError::not_found(format!("No route for {}", request.uri())))
+/// }
+///
+/// # async fn example() {
+/// let service = DynErrorService::<_, TextErrorResponse>::new(service_fn(handle));
+/// let response = service.oneshot(Request::new(Body::empty())).await.unwrap();
+/// assert_eq!(response.status(), 404);
+/// # }
+/// ```
+pub struct DynErrorService<S, I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// The inner service
+    inner: S,
+    /// Response for the error of the inner service becoming ready, sent
+    /// in response to the next request
+    ready_error: Option<Response>,
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<S, I: IntoHttpErrorResponse> DynErrorService<S, I> {
+    /// Wraps the provided service
+    pub fn new(inner: S) -> Self {
+        Self {
+            inner,
+            ready_error: None,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Unwraps the inner service
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S: Clone, I: IntoHttpErrorResponse> Clone for DynErrorService<S, I> {
+    fn clone(&self) -> Self {
+        Self::new(self.inner.clone())
+    }
+}
+
+impl<S, B, I> Service<Request<B>> for DynErrorService<S, I>
+where
+    S: Service<Request<B>>,
+    S::Response: IntoResponse,
+    S::Error: Into<DynHttpError<I>>,
+    I: IntoHttpErrorResponse,
+{
+    type Response = Response;
+    type Error = Infallible;
+    type Future = DynErrorFuture<S::Future, I>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        // Readiness errors are reported by the call that follows
+        match self.inner.poll_ready(cx) {
+            Poll::Ready(result) => {
+                if let Err(error) = result {
+                    self.ready_error = Some(error.into().into_response());
+                }
+                Poll::Ready(Ok(()))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        if let Some(response) = self.ready_error.take() {
+            return DynErrorFuture {
+                inner: None,
+                response: Some(response),
+                _marker: PhantomData,
+            };
+        }
+
+        DynErrorFuture {
+            inner: Some(self.inner.call(request)),
+            response: None,
+            _marker: PhantomData,
+        }
+    }
+}
+
+pin_project! {
+    /// Future returned by [DynErrorService]
+    pub struct DynErrorFuture<F, I> {
+        #[pin]
+        inner: Option<F>,
+        response: Option<Response>,
+        _marker: PhantomData<fn() -> I>,
+    }
+}
+
+impl<F, R, E, I> Future for DynErrorFuture<F, I>
+where
+    F: Future<Output = Result<R, E>>,
+    R: IntoResponse,
+    E: Into<DynHttpError<I>>,
+    I: IntoHttpErrorResponse,
+{
+    type Output = Result<Response, Infallible>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Some(response) = this.response.take() {
+            return Poll::Ready(Ok(response));
+        }
+
+        let Some(inner) = this.inner.as_pin_mut() else {
+            panic!("DynErrorFuture polled after completion");
+        };
+
+        let response = match inner.poll(cx) {
+            Poll::Ready(Ok(response)) => response.into_response(),
+            Poll::Ready(Err(error)) => error.into().into_response(),
+            Poll::Pending => return Poll::Pending,
+        };
+
+        Poll::Ready(Ok(response))
+    }
+}
@@ -0,0 +1,43 @@
+//! Support for the errors of `tower-sessions`
+//!
+//! Failures of the session store (i.e the database or cache holding the
+//! sessions being unreachable) are 503 Service Unavailable errors so that
+//! they are treated as transient outages, encoding and decoding failures
+//! are 500 Internal Server Errors
+
+use http::StatusCode;
+use tower_sessions::{session, session_store};
+
+use crate::HttpError;
+
+impl HttpError for session_store::Error {
+    fn status(&self) -> StatusCode {
+        match self {
+            session_store::Error::Backend(_) => StatusCode::SERVICE_UNAVAILABLE,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn reason(&self) -> String {
+        match self {
+            session_store::Error::Backend(_) => "Session store unavailable".to_string(),
+            _ => "Server error".to_string(),
+        }
+    }
+}
+
+impl HttpError for session::Error {
+    fn status(&self) -> StatusCode {
+        match self {
+            session::Error::Store(error) => error.status(),
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn reason(&self) -> String {
+        match self {
+            session::Error::Store(error) => error.reason(),
+            _ => "Server error".to_string(),
+        }
+    }
+}
@@ -0,0 +1,154 @@
+//! Layer for rejecting new requests once a graceful shutdown has begun
+
+use std::future::Future;
+use std::marker::PhantomData;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use axum_core::response::{IntoResponse, Response};
+use futures_util::future::{ready, Either, Ready};
+use http::Request;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{DynHttpError, IntoHttpErrorResponse, ShuttingDown, TextErrorResponse};
+
+/// Shared handle for the shutdown state of the server, clones of the
+/// handle share the same state
+///
+/// ```
+/// use axum::{Router, Server};
+/// use axum_dyn_error::{ShutdownLayer, ShutdownSignal, TextErrorResponse};
+///
+/// /// Mock future completing when the process receives a termination signal
+/// async fn terminate() {}
+///
+/// async fn serve() {
+///     let signal = ShutdownSignal::new();
+///     let app = Router::new().layer(ShutdownLayer::<TextErrorResponse>::new(signal.clone()));
+///
+///     Server::bind(&"0.0.0.0:3000".parse().unwrap())
+///         .serve(app.into_make_service())
+///         .with_graceful_shutdown(signal.wait_for(terminate()))
+///         .await
+///         .unwrap();
+/// }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ShutdownSignal {
+    /// Whether the shutdown has begun
+    state: Arc<AtomicBool>,
+}
+
+impl ShutdownSignal {
+    /// Creates a new handle for a server that isn't shutting down
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Marks the shutdown as begun, new requests are rejected with
+    /// [ShuttingDown]
+    pub fn begin(&self) {
+        self.state.store(true, Ordering::SeqCst);
+    }
+
+    /// Checks whether the shutdown has begun
+    pub fn is_shutting_down(&self) -> bool {
+        self.state.load(Ordering::SeqCst)
+    }
+
+    /// Wraps the provided shutdown signal future, beginning the shutdown
+    /// once it completes. The returned future can be passed to axum's
+    /// `with_graceful_shutdown`
+    pub fn wait_for<F>(&self, signal: F) -> impl Future<Output = ()> + Send + 'static
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let this = self.clone();
+        async move {
+            signal.await;
+            this.begin();
+        }
+    }
+}
+
+/// Layer that responds to all requests with a [ShuttingDown] error once
+/// the shutdown of its [ShutdownSignal] has begun
+pub struct ShutdownLayer<I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// The shutdown state
+    signal: ShutdownSignal,
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<I: IntoHttpErrorResponse> ShutdownLayer<I> {
+    /// Creates a new layer using the provided shutdown signal handle
+    pub fn new(signal: ShutdownSignal) -> Self {
+        Self {
+            signal,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<I: IntoHttpErrorResponse> Clone for ShutdownLayer<I> {
+    fn clone(&self) -> Self {
+        Self::new(self.signal.clone())
+    }
+}
+
+impl<S, I: IntoHttpErrorResponse> Layer<S> for ShutdownLayer<I> {
+    type Service = ShutdownService<S, I>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ShutdownService {
+            inner,
+            signal: self.signal.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Service created by [ShutdownLayer]
+pub struct ShutdownService<S, I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// The inner service
+    inner: S,
+    /// The shutdown state
+    signal: ShutdownSignal,
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<S: Clone, I: IntoHttpErrorResponse> Clone for ShutdownService<S, I> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            signal: self.signal.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S, B, I> Service<Request<B>> for ShutdownService<S, I>
+where
+    S: Service<Request<B>, Response = Response>,
+    I: IntoHttpErrorResponse,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Response, S::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        if self.signal.is_shutting_down() {
+            let response = DynHttpError::<I>::from(ShuttingDown).into_response();
+            return Either::Left(ready(Ok(response)));
+        }
+
+        Either::Right(self.inner.call(request))
+    }
+}
@@ -0,0 +1,207 @@
+//! Emission of structured events for every error converted into a
+//! response, for shipping errors to external systems
+
+use std::sync::{Arc, RwLock};
+use std::time::SystemTime;
+
+use http::{request::Parts, StatusCode};
+
+use crate::{ErrorKind, HttpError};
+
+/// Registered error sinks
+static SINKS: RwLock<Vec<Arc<dyn ErrorSink>>> = RwLock::new(Vec::new());
+
+/// Structured event for an error that was converted into a response
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ErrorEvent {
+    /// When the error occurred
+    pub timestamp: SystemTime,
+    /// The HTTP status code of the error
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::describe::serialize_status")
+    )]
+    pub status: StatusCode,
+    /// The broad category of the error
+    pub kind: ErrorKind,
+    /// Whether the error is transient
+    pub transient: bool,
+    /// The full type name of the underlying error
+    pub type_name: String,
+    /// The machine readable error code if the error has one
+    pub code: Option<String>,
+    /// The full error message
+    pub message: String,
+    /// The public reason of the error
+    pub reason: String,
+    /// Additional structured details about the error
+    #[cfg(feature = "json")]
+    pub details: Option<serde_json::Value>,
+    /// The method of the request if known
+    pub method: Option<String>,
+    /// The path of the request if known
+    pub path: Option<String>,
+    /// The ID of the request if known
+    pub request_id: Option<String>,
+}
+
+impl ErrorEvent {
+    /// Creates an event for the provided error and optional request parts,
+    /// sensitive fields within the details are redacted with the `secrecy`
+    /// feature (See `redact_details`)
+    pub fn from_error(error: &dyn HttpError, parts: Option<&Parts>) -> Self {
+        #[cfg(feature = "json")]
+        #[cfg_attr(not(feature = "secrecy"), allow(unused_mut))]
+        let mut details = error.details();
+
+        #[cfg(all(feature = "json", feature = "secrecy"))]
+        if let Some(details) = &mut details {
+            crate::secret::redact_details(details);
+        }
+
+        Self {
+            timestamp: SystemTime::now(),
+            status: error.status(),
+            kind: error.kind(),
+            transient: error.is_transient(),
+            type_name: error.type_name().to_string(),
+            code: error.code().map(str::to_string),
+            message: error.to_string(),
+            reason: error.reason(),
+            #[cfg(feature = "json")]
+            details,
+            method: parts.map(|parts| parts.method.to_string()),
+            path: parts.map(|parts| parts.uri.path().to_string()),
+            request_id: parts
+                .and_then(|parts| parts.headers.get("x-request-id"))
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string),
+        }
+    }
+
+    /// Fingerprint of the error from its type, status and code, errors
+    /// with the same fingerprint are considered the same error. The
+    /// fingerprint is a 64-bit FNV-1a hash which is stable across Rust
+    /// releases, platforms and restarts, though it changes if the type is
+    /// renamed or moved to another module
+    pub fn fingerprint(&self) -> u64 {
+        crate::kind::fingerprint_of(&self.type_name, self.status, self.code.as_deref())
+    }
+}
+
+/// Destination for [ErrorEvent]s, sinks are called while the response is
+/// being created so they must not block (See [ChannelErrorSink] for handing
+/// events off to a background task). Sinks receive every error, including
+/// errors whose logs were suppressed by `ErrorConfig::dedup_window`
+pub trait ErrorSink: Send + Sync + 'static {
+    /// Sends the provided event
+    fn send(&self, event: &ErrorEvent);
+}
+
+impl<F> ErrorSink for F
+where
+    F: Fn(&ErrorEvent) + Send + Sync + 'static,
+{
+    fn send(&self, event: &ErrorEvent) {
+        self(event)
+    }
+}
+
+/// Registry of [ErrorSink]s that receive an event for every error converted
+/// into a response, events are created before any rewrites are applied
+pub struct ErrorSinks;
+
+impl ErrorSinks {
+    /// Registers a sink that will receive error events
+    pub fn register<S: ErrorSink>(sink: S) {
+        SINKS
+            .write()
+            .unwrap_or_else(|err| err.into_inner())
+            .push(Arc::new(sink));
+    }
+
+    /// Removes all the registered sinks
+    pub fn clear() {
+        SINKS.write().unwrap_or_else(|err| err.into_inner()).clear();
+    }
+
+    /// Sends an event for the error to the registered sinks
+    #[cfg(feature = "axum")]
+    pub(crate) fn emit(error: &dyn HttpError, parts: Option<&Parts>) {
+        let sinks = SINKS.read().unwrap_or_else(|err| err.into_inner()).clone();
+        if sinks.is_empty() {
+            return;
+        }
+
+        let event = ErrorEvent::from_error(error, parts);
+        for sink in sinks {
+            sink.send(&event);
+        }
+    }
+}
+
+#[cfg(feature = "tower")]
+pub use channel::ChannelErrorSink;
+
+#[cfg(feature = "tower")]
+mod channel {
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::sync::Arc;
+
+    use tokio::sync::mpsc;
+
+    use super::{ErrorEvent, ErrorSink};
+
+    /// [ErrorSink] that sends events through a bounded channel to be
+    /// processed by a background task, events are dropped instead of
+    /// blocking the response when the channel is full
+    ///
+    /// ```
+    /// use axum_dyn_error::{ChannelErrorSink, ErrorSinks};
+    ///
+    /// # async fn example() {
+    /// let (sink, mut events) = ChannelErrorSink::new(1024);
+    /// ErrorSinks::register(sink);
+    ///
+    /// tokio::spawn(async move {
+    ///     while let Some(event) = events.recv().await {
+    ///         // Ship the event to an external system
+    ///     }
+    /// });
+    /// # }
+    /// ```
+    #[derive(Clone)]
+    pub struct ChannelErrorSink {
+        /// Sender for the channel
+        sender: mpsc::Sender<ErrorEvent>,
+        /// Number of events dropped because the channel was full or closed
+        dropped: Arc<AtomicU64>,
+    }
+
+    impl ChannelErrorSink {
+        /// Creates a new sink with a channel that can buffer `capacity`
+        /// events, returning the sink and the receiver for the events
+        pub fn new(capacity: usize) -> (Self, mpsc::Receiver<ErrorEvent>) {
+            let (sender, receiver) = mpsc::channel(capacity);
+            let sink = Self {
+                sender,
+                dropped: Arc::new(AtomicU64::new(0)),
+            };
+            (sink, receiver)
+        }
+
+        /// Number of events dropped because the channel was full or closed
+        pub fn dropped(&self) -> u64 {
+            self.dropped.load(Ordering::Relaxed)
+        }
+    }
+
+    impl ErrorSink for ChannelErrorSink {
+        fn send(&self, event: &ErrorEvent) {
+            if self.sender.try_send(event.clone()).is_err() {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+    }
+}
@@ -0,0 +1,174 @@
+//! Wrappers and extension traits for snafu support
+//!
+//! Errors defined using snafu's derive can implement [HttpError] directly,
+//! this module provides support for snafu's [Whatever] error type
+
+use std::error::Error;
+use std::fmt::{Debug, Display};
+
+use http::StatusCode;
+use snafu::{ErrorCompat, Whatever};
+
+use crate::{DynHttpError, ErrorMapper, HttpError, IntoHttpError, IntoHttpErrorResponse};
+
+/// Wrapper around a [Whatever] error allowing it to be used as a [HttpError]
+///
+/// [Whatever] errors can't be sent between threads so the message, error
+/// chain and backtrace are captured when the wrapper is created. The message
+/// is only included in logs, responses use a generic message unless a
+/// public message is provided (See [WhateverStatusExt::status_msg])
+pub struct WhateverHttpError {
+    /// The message of the error
+    message: String,
+    /// Messages of the errors in the source chain
+    chain: Vec<String>,
+    /// The captured backtrace
+    backtrace: Option<String>,
+    /// The response status code
+    status: StatusCode,
+    /// Public reason to use instead of the default
+    reason: Option<String>,
+}
+
+impl WhateverHttpError {
+    /// Creates a new wrapper capturing the details of the error
+    fn new(error: &Whatever, status: StatusCode, reason: Option<String>) -> Self {
+        let mut chain = Vec::new();
+        let mut source = error.source();
+        while let Some(error) = source {
+            chain.push(error.to_string());
+            source = error.source();
+        }
+
+        Self {
+            message: error.to_string(),
+            chain,
+            backtrace: ErrorCompat::backtrace(error).map(ToString::to_string),
+            status,
+            reason,
+        }
+    }
+
+    /// Creates a wrapper using the status from the [ErrorMapper], falling
+    /// back to the default status (See [ErrorConfig::default_status](crate::ErrorConfig::default_status))
+    fn from_whatever(error: &Whatever) -> Self {
+        match ErrorMapper::map(error) {
+            Some(mapping) => Self::new(error, mapping.status, mapping.reason),
+            None => Self::new(error, crate::config::default_status(), None),
+        }
+    }
+
+    /// Messages of the errors in the source chain of the original error
+    pub fn chain(&self) -> &[String] {
+        &self.chain
+    }
+
+    /// The backtrace captured by the original error
+    pub fn backtrace(&self) -> Option<&str> {
+        self.backtrace.as_deref()
+    }
+}
+
+impl Debug for WhateverHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)?;
+        for cause in &self.chain {
+            write!(f, "\n\nCaused by:\n    {cause}")?;
+        }
+        Ok(())
+    }
+}
+
+impl Display for WhateverHttpError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl Error for WhateverHttpError {}
+
+impl HttpError for WhateverHttpError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        // The debug variant contains the error chain
+        log::error!("{self:?}{}", crate::location::log_suffix());
+    }
+
+    fn status(&self) -> StatusCode {
+        self.status
+    }
+
+    fn reason(&self) -> String {
+        match &self.reason {
+            Some(reason) => reason.clone(),
+            None => "Server error".to_string(),
+        }
+    }
+}
+
+/// Allow conversion from [Whatever] errors into [DynHttpError] by wrapping
+/// them with [WhateverHttpError]
+///
+/// The [ErrorMapper] is consulted for the status of the error, falling
+/// back to the default status (See [ErrorConfig::default_status](crate::ErrorConfig::default_status))
+impl<I> From<Whatever> for DynHttpError<I>
+where
+    I: IntoHttpErrorResponse,
+{
+    #[track_caller]
+    fn from(value: Whatever) -> Self {
+        WhateverHttpError::from_whatever(&value).into()
+    }
+}
+
+impl IntoHttpError for Whatever {
+    fn into_http_error(self) -> Box<dyn HttpError> {
+        Box::new(WhateverHttpError::from_whatever(&self))
+    }
+}
+
+/// Extension for adding a [StatusCode] to a [Whatever] error
+pub trait WhateverStatusExt {
+    /// Add an additional status code to the error response
+    fn status(self, status: StatusCode) -> WhateverHttpError;
+
+    /// Add an additional status code and a public message to the error
+    /// response, the message is used as the response reason
+    fn status_msg(self, status: StatusCode, message: impl Into<String>) -> WhateverHttpError;
+}
+
+impl WhateverStatusExt for Whatever {
+    fn status(self, status: StatusCode) -> WhateverHttpError {
+        WhateverHttpError::new(&self, status, None)
+    }
+
+    fn status_msg(self, status: StatusCode, message: impl Into<String>) -> WhateverHttpError {
+        WhateverHttpError::new(&self, status, Some(message.into()))
+    }
+}
+
+/// Extension for adding a [StatusCode] to results containing a [Whatever]
+/// error, for use at the end of snafu context selector chains
+///
+/// ```
+/// use axum_dyn_error::{HttpResult, StatusCode, WhateverResultExt};
+/// use snafu::ResultExt;
+///
+/// fn parse_id(id: &str) -> HttpResult<u32> {
+///     let id = id
+///         .parse::<u32>()
+///         .whatever_context("Invalid id")
+///         .http_status(StatusCode::BAD_REQUEST)?;
+///     Ok(id)
+/// }
+/// ```
+pub trait WhateverResultExt<T> {
+    /// Add an additional status code to the error response
+    fn http_status(self, status: StatusCode) -> Result<T, WhateverHttpError>;
+}
+
+impl<T> WhateverResultExt<T> for Result<T, Whatever> {
+    fn http_status(self, status: StatusCode) -> Result<T, WhateverHttpError> {
+        self.map_err(|error| error.status(status))
+    }
+}
@@ -0,0 +1,147 @@
+//! Service for deployments that failed to start, responding to every
+//! request with a 503 error in the standard error format while the
+//! orchestrator restarts the deployment
+
+use std::convert::Infallible;
+use std::error::Error;
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use axum_core::response::{IntoResponse, Response};
+use futures_util::future::{ready, Ready};
+use http::{header::RETRY_AFTER, HeaderMap, HeaderValue, Request, StatusCode};
+use tower_service::Service;
+
+use crate::{DynHttpError, HttpError, IntoHttpErrorResponse, TextErrorResponse};
+
+/// Error for a service that failed to configure or bootstrap (503 Service
+/// Unavailable), the configuration error is only included in logs
+///
+/// ```
+/// use axum::Router;
+/// use axum_dyn_error::{StartupError, TextErrorResponse};
+///
+/// fn load_config() -> Result<String, std::io::Error> {
+///     Err(std::io::Error::other("missing DATABASE_URL"))
+/// }
+///
+/// fn app() -> Router {
+///     match load_config() {
+///         Ok(_config) => Router::new(),
+///         Err(error) => {
+///             let service = StartupError::new(error).into_service::<TextErrorResponse>();
+///             Router::new().fallback_service(service)
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct StartupError {
+    /// The error that caused the startup failure
+    message: String,
+    /// Duration clients should wait before retrying
+    retry_after: Option<Duration>,
+}
+
+impl StartupError {
+    /// Creates a new startup error from the error that caused the
+    /// failure, sources of the error are included in the message
+    pub fn new<E: Error>(error: E) -> Self {
+        let mut message = error.to_string();
+        let mut source = error.source();
+        while let Some(error) = source {
+            message.push_str(": ");
+            message.push_str(&error.to_string());
+            source = error.source();
+        }
+
+        Self {
+            message,
+            retry_after: None,
+        }
+    }
+
+    /// Sets the duration clients should wait before retrying which is
+    /// sent as the `Retry-After` header
+    pub fn retry_after(mut self, duration: Duration) -> Self {
+        self.retry_after = Some(duration);
+        self
+    }
+
+    /// Logs the startup failure and creates a service responding to every
+    /// request with this error
+    pub fn into_service<I: IntoHttpErrorResponse>(self) -> StartupErrorService<I> {
+        #[cfg(feature = "log")]
+        log::error!("{self}");
+
+        StartupErrorService {
+            error: self,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl Display for StartupError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Service failed to start: {}", self.message)
+    }
+}
+
+impl Error for StartupError {}
+
+impl HttpError for StartupError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        // The failure is logged once when the service is created
+        log::debug!("{self}");
+    }
+
+    fn status(&self) -> StatusCode {
+        StatusCode::SERVICE_UNAVAILABLE
+    }
+
+    fn reason(&self) -> String {
+        "Service unavailable".to_string()
+    }
+
+    fn headers(&self, headers: &mut HeaderMap) {
+        if let Some(retry_after) = self.retry_after {
+            headers.insert(RETRY_AFTER, HeaderValue::from(retry_after.as_secs()));
+        }
+    }
+}
+
+/// Service responding to every request with a [StartupError] using the
+/// formatter `I`, created by [StartupError::into_service]
+pub struct StartupErrorService<I: IntoHttpErrorResponse = TextErrorResponse> {
+    /// The startup error
+    error: StartupError,
+    /// Marker for storing the [IntoHttpErrorResponse] type
+    _marker: PhantomData<fn() -> I>,
+}
+
+impl<I: IntoHttpErrorResponse> Clone for StartupErrorService<I> {
+    fn clone(&self) -> Self {
+        Self {
+            error: self.error.clone(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<B, I: IntoHttpErrorResponse> Service<Request<B>> for StartupErrorService<I> {
+    type Response = Response;
+    type Error = Infallible;
+    type Future = Ready<Result<Response, Infallible>>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _request: Request<B>) -> Self::Future {
+        let response = DynHttpError::<I>::from(self.error.clone()).into_response();
+        ready(Ok(response))
+    }
+}
@@ -0,0 +1,105 @@
+//! Canonical reason phrases and validation of numeric status codes
+
+use http::StatusCode;
+
+/// Obtains the canonical reason phrase for the status code (i.e
+/// `StatusCode::NOT_FOUND` is "Not Found"), used as the default title of
+/// error pages and problem details. Status codes without a canonical
+/// reason use a phrase for their class (i.e "Client Error" for 4xx)
+///
+/// ```
+/// use axum_dyn_error::{reason_phrase, StatusCode};
+///
+/// assert_eq!(reason_phrase(StatusCode::NOT_FOUND), "Not Found");
+/// assert_eq!(reason_phrase(StatusCode::from_u16(499).unwrap()), "Client Error");
+/// ```
+pub fn reason_phrase(status: StatusCode) -> &'static str {
+    if let Some(reason) = status.canonical_reason() {
+        return reason;
+    }
+
+    match status.as_u16() {
+        100..=199 => "Informational",
+        200..=299 => "Success",
+        300..=399 => "Redirection",
+        400..=499 => "Client Error",
+        _ => "Server Error",
+    }
+}
+
+/// Validates a numeric status code used for an error, panicking when the
+/// status isn't a valid status code (100 to 999) or is an informational
+/// or successful status (1xx and 2xx) that can't describe an error.
+///
+/// Used by [define_http_errors!](crate::define_http_errors) in a constant
+/// so that invalid statuses fail to compile
+///
+/// ```
+/// use axum_dyn_error::validate_error_status;
+///
+/// const STATUS: u16 = validate_error_status(404);
+/// ```
+///
+/// ```compile_fail
+/// use axum_dyn_error::validate_error_status;
+///
+/// const STATUS: u16 = validate_error_status(200);
+/// ```
+pub const fn validate_error_status(status: u16) -> u16 {
+    assert!(
+        status >= 100 && status < 1000,
+        "Invalid status code, status codes must be between 100 and 999"
+    );
+    assert!(
+        status >= 300,
+        "Invalid error status code, informational and successful statuses can't be errors"
+    );
+    status
+}
+
+/// Parses a numeric status code used for an error, returns [None] when
+/// the status isn't valid (See [validate_error_status]), for statuses
+/// that are only known at runtime such as ones loaded from configuration
+///
+/// ```
+/// use axum_dyn_error::{parse_error_status, StatusCode};
+///
+/// assert_eq!(parse_error_status(502), Some(StatusCode::BAD_GATEWAY));
+/// assert_eq!(parse_error_status(204), None);
+/// assert_eq!(parse_error_status(1000), None);
+/// ```
+pub fn parse_error_status(status: u16) -> Option<StatusCode> {
+    if status < 300 {
+        return None;
+    }
+
+    StatusCode::from_u16(status).ok()
+}
+
+/// Whether errors with the status are security relevant by default
+/// (See [HttpError::security_relevant](crate::HttpError::security_relevant))
+pub(crate) fn is_security_relevant_status(status: StatusCode) -> bool {
+    matches!(status, StatusCode::UNAUTHORIZED | StatusCode::FORBIDDEN)
+}
+
+/// Whether responses with the status must not have a body (1xx
+/// informational, 204 No Content and 304 Not Modified)
+/// (See [HttpError::bodiless](crate::HttpError::bodiless))
+pub(crate) fn is_bodiless_status(status: StatusCode) -> bool {
+    status.is_informational()
+        || status == StatusCode::NO_CONTENT
+        || status == StatusCode::NOT_MODIFIED
+}
+
+/// Whether errors with the status are transient by default
+/// (See [HttpError::is_transient](crate::HttpError::is_transient))
+pub(crate) fn is_transient_status(status: StatusCode) -> bool {
+    matches!(
+        status,
+        StatusCode::REQUEST_TIMEOUT
+            | StatusCode::TOO_MANY_REQUESTS
+            | StatusCode::BAD_GATEWAY
+            | StatusCode::SERVICE_UNAVAILABLE
+            | StatusCode::GATEWAY_TIMEOUT
+    )
+}
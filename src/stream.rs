@@ -0,0 +1,117 @@
+//! Adapters for annotating the errors of streams of results, so that
+//! streaming handlers don't need a closure at every combinator
+
+use std::fmt::Display;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+
+use futures_util::stream::{Stream, TryStream};
+use http::StatusCode;
+use pin_project_lite::pin_project;
+
+use crate::{DynHttpError, IntoHttpErrorResponse};
+
+/// Extension trait for streams of results whose errors can be converted
+/// into a [DynHttpError]
+///
+/// ```
+/// use axum_dyn_error::{HttpResult, HttpStreamExt, MessageHttpError, StatusCode};
+/// use futures_util::stream::{self, Stream};
+///
+/// fn chunks() -> impl Stream<Item = HttpResult<Vec<u8>>> {
+///     stream::iter([
+///         Ok(vec![1, 2]),
+///         Err(MessageHttpError::new(StatusCode::BAD_REQUEST, "Invalid chunk")),
+///     ])
+///     .map_http_err(StatusCode::UNPROCESSABLE_ENTITY)
+///     .http_context("reading chunks")
+/// }
+/// ```
+pub trait HttpStreamExt: TryStream + Sized {
+    /// Converts the errors of the stream into [DynHttpError]s with the
+    /// provided status (See [DynHttpError::map_status])
+    fn map_http_err<I>(self, status: StatusCode) -> MapHttpErr<Self, I>
+    where
+        Self::Error: Into<DynHttpError<I>>,
+        I: IntoHttpErrorResponse,
+    {
+        MapHttpErr {
+            stream: self,
+            status,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Converts the errors of the stream into [DynHttpError]s with the
+    /// provided context (See [DynHttpError::context])
+    fn http_context<I, C>(self, context: C) -> HttpContext<Self, C, I>
+    where
+        Self::Error: Into<DynHttpError<I>>,
+        I: IntoHttpErrorResponse,
+        C: Display,
+    {
+        HttpContext {
+            stream: self,
+            context,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<S: TryStream> HttpStreamExt for S {}
+
+pin_project! {
+    /// Stream returned by [HttpStreamExt::map_http_err]
+    pub struct MapHttpErr<S, I> {
+        #[pin]
+        stream: S,
+        status: StatusCode,
+        _marker: PhantomData<fn() -> I>,
+    }
+}
+
+impl<S, I> Stream for MapHttpErr<S, I>
+where
+    S: TryStream,
+    S::Error: Into<DynHttpError<I>>,
+    I: IntoHttpErrorResponse,
+{
+    type Item = Result<S::Ok, DynHttpError<I>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let status = *this.status;
+        this.stream
+            .try_poll_next(cx)
+            .map(|item| item.map(|result| result.map_err(|error| error.into().map_status(status))))
+    }
+}
+
+pin_project! {
+    /// Stream returned by [HttpStreamExt::http_context]
+    pub struct HttpContext<S, C, I> {
+        #[pin]
+        stream: S,
+        context: C,
+        _marker: PhantomData<fn() -> I>,
+    }
+}
+
+impl<S, C, I> Stream for HttpContext<S, C, I>
+where
+    S: TryStream,
+    S::Error: Into<DynHttpError<I>>,
+    C: Display,
+    I: IntoHttpErrorResponse,
+{
+    type Item = Result<S::Ok, DynHttpError<I>>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let context = &*this.context;
+        this.stream
+            .try_poll_next(cx)
+            .map(|item| item.map(|result| result.map_err(|error| error.into().context(context))))
+    }
+}
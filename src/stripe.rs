@@ -0,0 +1,72 @@
+//! Support for the errors of the Stripe API (`async-stripe`)
+//!
+//! Declined cards are 402 Payment Required errors and rate limits are 429
+//! Too Many Requests errors. Authentication, configuration and invalid
+//! request errors are caused by the service rather than the client so they
+//! are server errors with a generic reason, the error reported by Stripe is
+//! only included in logs
+
+use http::StatusCode;
+use stripe::{ErrorType, RequestError, StripeError};
+
+use crate::HttpError;
+
+/// Card errors keep the message from Stripe which is intended to be shown
+/// to customers and include the error and decline codes in the details
+impl HttpError for StripeError {
+    #[cfg(feature = "log")]
+    fn log(&self) {
+        if self.status().is_server_error() {
+            log::error!("{self}{}", crate::location::log_suffix());
+        } else {
+            log::debug!("{self}");
+        }
+    }
+
+    fn status(&self) -> StatusCode {
+        match self {
+            StripeError::Stripe(error) => match error.error_type {
+                ErrorType::Card => StatusCode::PAYMENT_REQUIRED,
+                ErrorType::RateLimit => StatusCode::TOO_MANY_REQUESTS,
+                ErrorType::Api | ErrorType::Connection => StatusCode::BAD_GATEWAY,
+                _ => StatusCode::INTERNAL_SERVER_ERROR,
+            },
+            StripeError::ClientError(_) => StatusCode::BAD_GATEWAY,
+            StripeError::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn reason(&self) -> String {
+        match self {
+            StripeError::Stripe(RequestError {
+                error_type: ErrorType::Card,
+                message,
+                ..
+            }) => message
+                .clone()
+                .unwrap_or_else(|| "Payment declined".to_string()),
+            StripeError::Stripe(RequestError {
+                error_type: ErrorType::RateLimit,
+                ..
+            }) => "Too many payment requests".to_string(),
+            _ => "Server error".to_string(),
+        }
+    }
+
+    #[cfg(feature = "json")]
+    fn details(&self) -> Option<serde_json::Value> {
+        match self {
+            StripeError::Stripe(RequestError {
+                error_type: ErrorType::Card,
+                code,
+                decline_code,
+                ..
+            }) => Some(serde_json::json!({
+                "code": code.map(|code| code.to_string()),
+                "decline_code": decline_code,
+            })),
+            _ => None,
+        }
+    }
+}
@@ -0,0 +1,236 @@
+//! Periodic summaries of the most frequent errors, for noticing
+//! regressions without a dashboard
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+
+use http::StatusCode;
+use tokio::task::JoinHandle;
+use tokio::time::{interval_at, Instant, MissedTickBehavior};
+
+use crate::{ErrorEvent, ErrorSinks};
+
+/// Function for emitting a summary
+type SummaryFn = Arc<dyn Fn(&ErrorSummary) + Send + Sync>;
+
+/// Error counts aggregated by fingerprint
+type Counts = Mutex<HashMap<u64, ErrorSummaryEntry>>;
+
+/// Occurrences of a single error within an [ErrorSummary]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ErrorSummaryEntry {
+    /// Fingerprint of the error (See [ErrorEvent::fingerprint])
+    pub fingerprint: u64,
+    /// Number of times the error occurred
+    pub count: u64,
+    /// The HTTP status code of the error
+    #[cfg_attr(
+        feature = "serde",
+        serde(serialize_with = "crate::describe::serialize_status")
+    )]
+    pub status: StatusCode,
+    /// The full type name of the error
+    pub type_name: String,
+    /// The machine readable error code if the error has one
+    pub code: Option<String>,
+    /// The message of the most recent occurrence
+    pub message: String,
+    /// When the error first occurred within the period
+    pub first_seen: SystemTime,
+    /// When the error last occurred within the period
+    pub last_seen: SystemTime,
+}
+
+/// Summary of the errors that occurred within a period
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ErrorSummary {
+    /// Length of the period
+    pub period: Duration,
+    /// Total number of errors within the period
+    pub total: u64,
+    /// The most frequent errors ordered by their count
+    pub entries: Vec<ErrorSummaryEntry>,
+}
+
+/// Background task aggregating error counts by fingerprint and emitting
+/// a summary of the most frequent errors at a fixed interval, periods
+/// without any errors are skipped
+///
+/// By default the summary of the top 10 errors is logged every hour
+/// (Requires the `log` feature)
+///
+/// ```no_run
+/// use std::time::Duration;
+/// use axum_dyn_error::{ErrorSummary, ErrorSummaryReporter};
+///
+/// # async fn example() {
+/// ErrorSummaryReporter::new()
+///     .interval(Duration::from_secs(60 * 60 * 24))
+///     .top(5)
+///     .on_summary(|summary: &ErrorSummary| {
+///         // Send the summary somewhere
+///     })
+///     .spawn();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct ErrorSummaryReporter {
+    /// How often summaries are emitted
+    interval: Duration,
+    /// Number of errors included in the summary
+    top: usize,
+    /// Function for emitting the summary
+    on_summary: SummaryFn,
+}
+
+impl ErrorSummaryReporter {
+    /// Creates a new reporter logging the top 10 errors every hour
+    pub fn new() -> Self {
+        Self {
+            interval: Duration::from_secs(60 * 60),
+            top: 10,
+            on_summary: Arc::new(log_summary),
+        }
+    }
+
+    /// Sets how often summaries are emitted
+    pub fn interval(mut self, interval: Duration) -> Self {
+        self.interval = interval.max(Duration::from_millis(1));
+        self
+    }
+
+    /// Sets the number of errors included in the summary
+    pub fn top(mut self, top: usize) -> Self {
+        self.top = top;
+        self
+    }
+
+    /// Sets the function for emitting the summary instead of logging it
+    pub fn on_summary<F>(mut self, on_summary: F) -> Self
+    where
+        F: Fn(&ErrorSummary) + Send + Sync + 'static,
+    {
+        self.on_summary = Arc::new(on_summary);
+        self
+    }
+
+    /// Registers an [ErrorSink](crate::ErrorSink) for counting the errors and
+    /// spawns the task that emits the summaries, the task stops after emitting
+    /// a final summary once the sink is removed using [ErrorSinks::clear]
+    pub fn spawn(self) -> JoinHandle<()> {
+        let counts: Arc<Counts> = Arc::default();
+        let sink_counts = counts.clone();
+
+        ErrorSinks::register(move |event: &ErrorEvent| record(&sink_counts, event));
+
+        tokio::spawn(self.run(counts))
+    }
+
+    /// Emits the summaries until the sink holding the other reference
+    /// to the counts is dropped
+    async fn run(self, counts: Arc<Counts>) {
+        let mut interval = interval_at(Instant::now() + self.interval, self.interval);
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            let stopped = Arc::strong_count(&counts) == 1;
+            let entries = {
+                let mut counts = counts.lock().unwrap_or_else(|err| err.into_inner());
+                std::mem::take(&mut *counts)
+            };
+
+            if let Some(summary) = self.summarize(entries) {
+                (self.on_summary)(&summary);
+            }
+
+            if stopped {
+                return;
+            }
+        }
+    }
+
+    /// Creates the summary for the counts of a period
+    fn summarize(&self, entries: HashMap<u64, ErrorSummaryEntry>) -> Option<ErrorSummary> {
+        let total = entries.values().map(|entry| entry.count).sum();
+        if total == 0 {
+            return None;
+        }
+
+        let mut entries: Vec<ErrorSummaryEntry> = entries.into_values().collect();
+        entries.sort_by_key(|entry| std::cmp::Reverse(entry.count));
+        entries.truncate(self.top);
+
+        Some(ErrorSummary {
+            period: self.interval,
+            total,
+            entries,
+        })
+    }
+}
+
+impl Default for ErrorSummaryReporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Counts an occurrence of the error
+fn record(counts: &Counts, event: &ErrorEvent) {
+    let mut counts = counts.lock().unwrap_or_else(|err| err.into_inner());
+    let fingerprint = event.fingerprint();
+
+    let entry = counts
+        .entry(fingerprint)
+        .or_insert_with(|| ErrorSummaryEntry {
+            fingerprint,
+            count: 0,
+            status: event.status,
+            type_name: event.type_name.clone(),
+            code: event.code.clone(),
+            message: String::new(),
+            first_seen: event.timestamp,
+            last_seen: event.timestamp,
+        });
+
+    entry.count += 1;
+    entry.message.clone_from(&event.message);
+    entry.last_seen = event.timestamp;
+}
+
+/// Logs the summary as a ranked list of the errors
+fn log_summary(_summary: &ErrorSummary) {
+    #[cfg(feature = "log")]
+    {
+        use std::fmt::Write;
+
+        let summary = _summary;
+        let mut message = format!(
+            "{} error(s) in the last {:?}, top {}:",
+            summary.total,
+            summary.period,
+            summary.entries.len()
+        );
+
+        for (index, entry) in summary.entries.iter().enumerate() {
+            let _ = write!(
+                message,
+                "\n  {}. {}x {} {}",
+                index + 1,
+                entry.count,
+                entry.status.as_u16(),
+                entry.type_name
+            );
+            if let Some(code) = &entry.code {
+                let _ = write!(message, " ({code})");
+            }
+            let _ = write!(message, ": {}", entry.message);
+        }
+
+        log::warn!("{message}");
+    }
+}
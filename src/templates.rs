@@ -0,0 +1,134 @@
+//! Adapter formatters that render error pages using the application's
+//! template engine (`askama`, `maud` and `minijinja` features), each
+//! template is provided an [ErrorPageContext] describing the error
+
+use std::marker::PhantomData;
+
+use axum_core::response::Response;
+
+use crate::{html::html_page_response, HttpError, IntoHttpErrorResponse};
+
+/// Context provided to error page templates
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ErrorPageContext {
+    /// The HTTP status code
+    pub status: u16,
+    /// The canonical reason phrase for the status code (i.e "Not Found")
+    pub title: String,
+    /// The reason for the error
+    pub reason: String,
+    /// The machine readable error code
+    pub code: Option<String>,
+    /// The default locale of the current [ErrorPolicy](crate::ErrorPolicy)
+    /// (`tower` feature) for templates that support localization
+    pub locale: Option<String>,
+}
+
+impl ErrorPageContext {
+    /// Creates the context for the provided error
+    pub fn from_error(error: &dyn HttpError) -> Self {
+        let status = error.status();
+        Self {
+            status: status.as_u16(),
+            title: crate::reason_phrase(status).to_string(),
+            reason: error.reason(),
+            code: error.code().map(str::to_string),
+            #[cfg(feature = "tower")]
+            locale: crate::ErrorPolicy::current()
+                .and_then(|policy| policy.locale().map(str::to_string)),
+            #[cfg(not(feature = "tower"))]
+            locale: None,
+        }
+    }
+}
+
+/// Formatter that renders error pages using the askama template `T`
+/// which is created from the [ErrorPageContext]
+#[cfg(feature = "askama")]
+pub struct AskamaErrorResponse<T> {
+    /// Marker for storing the template type
+    _marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "askama")]
+impl<T> IntoHttpErrorResponse for AskamaErrorResponse<T>
+where
+    T: askama::Template + From<ErrorPageContext>,
+{
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        let context = ErrorPageContext::from_error(error.as_ref());
+        render_page(error.status(), T::from(context).render())
+    }
+}
+
+/// Error page that can be rendered by the [MaudErrorResponse] formatter
+#[cfg(feature = "maud")]
+pub trait MaudErrorPage {
+    /// Renders the error page markup for the provided context
+    fn render(context: &ErrorPageContext) -> maud::Markup;
+}
+
+/// Formatter that renders error pages using the maud page `T`
+#[cfg(feature = "maud")]
+pub struct MaudErrorResponse<T> {
+    /// Marker for storing the page type
+    _marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "maud")]
+impl<T: MaudErrorPage> IntoHttpErrorResponse for MaudErrorResponse<T> {
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        let context = ErrorPageContext::from_error(error.as_ref());
+        html_page_response(error.status(), T::render(&context).into_string())
+    }
+}
+
+/// Error page template that can be rendered by the [MiniJinjaErrorResponse]
+/// formatter
+#[cfg(feature = "minijinja")]
+pub trait MiniJinjaErrorPage {
+    /// Name of the template to render
+    const TEMPLATE: &'static str;
+
+    /// The environment containing the template
+    fn environment() -> &'static minijinja::Environment<'static>;
+}
+
+/// Formatter that renders error pages using the minijinja template from `T`,
+/// the [ErrorPageContext] is provided as the template context
+#[cfg(feature = "minijinja")]
+pub struct MiniJinjaErrorResponse<T> {
+    /// Marker for storing the template type
+    _marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "minijinja")]
+impl<T: MiniJinjaErrorPage> IntoHttpErrorResponse for MiniJinjaErrorResponse<T> {
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        let context = ErrorPageContext::from_error(error.as_ref());
+        let rendered = T::environment()
+            .get_template(T::TEMPLATE)
+            .and_then(|template| template.render(&context));
+        render_page(error.status(), rendered)
+    }
+}
+
+/// Creates the response for a rendered page, falling back to an empty
+/// server error response if rendering failed
+#[cfg(any(feature = "askama", feature = "minijinja"))]
+fn render_page<E: std::fmt::Display>(
+    status: http::StatusCode,
+    rendered: Result<String, E>,
+) -> Response {
+    use axum_core::response::IntoResponse;
+
+    match rendered {
+        Ok(page) => html_page_response(status, page),
+        Err(_err) => {
+            #[cfg(feature = "log")]
+            log::error!("Failed to render error page: {_err}");
+            http::StatusCode::INTERNAL_SERVER_ERROR.into_response()
+        }
+    }
+}
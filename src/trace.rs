@@ -0,0 +1,222 @@
+//! Layer logging a single line for each request, including the identity
+//! of the error when the response was created from a [DynHttpError](crate::DynHttpError)
+
+use std::fmt::Write;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Instant;
+
+use http::{HeaderValue, Method, Request, Response};
+use log::Level;
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::{ErrorClassification, ErrorIdentity, REQUEST_ID_HEADER};
+
+/// Layer that logs one line per request with the method, path, status
+/// and latency of the request. When the response was created from a
+/// [DynHttpError](crate::DynHttpError) the kind, type, code and
+/// fingerprint of the error are included (See [ErrorIdentity]).
+///
+/// Server errors (5xx) and failures of the inner service are logged at
+/// the failure level (Default: [Level::Warn]), other responses at the
+/// success level (Default: [Level::Info])
+///
+/// ```
+/// use axum::{routing::get, Router};
+/// use axum_dyn_error::DynErrorTraceLayer;
+///
+/// let app: Router = Router::new()
+///     .route("/", get(|| async { "Hello" }))
+///     .layer(DynErrorTraceLayer::new());
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct DynErrorTraceLayer {
+    /// Level for successful responses
+    success_level: Level,
+    /// Level for server errors and failures of the inner service
+    failure_level: Level,
+}
+
+impl DynErrorTraceLayer {
+    /// Creates a new layer using the default levels
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the level used for responses that aren't server errors
+    pub fn success_level(mut self, level: Level) -> Self {
+        self.success_level = level;
+        self
+    }
+
+    /// Sets the level used for server errors and failures of the
+    /// inner service
+    pub fn failure_level(mut self, level: Level) -> Self {
+        self.failure_level = level;
+        self
+    }
+}
+
+impl Default for DynErrorTraceLayer {
+    fn default() -> Self {
+        Self {
+            success_level: Level::Info,
+            failure_level: Level::Warn,
+        }
+    }
+}
+
+impl<S> Layer<S> for DynErrorTraceLayer {
+    type Service = DynErrorTraceService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DynErrorTraceService {
+            inner,
+            layer: *self,
+        }
+    }
+}
+
+/// Service created by [DynErrorTraceLayer]
+#[derive(Debug, Clone)]
+pub struct DynErrorTraceService<S> {
+    /// The inner service
+    inner: S,
+    /// The layer configuration
+    layer: DynErrorTraceLayer,
+}
+
+impl<S, B, ResBody> Service<Request<B>> for DynErrorTraceService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = DynErrorTraceFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let details = RequestDetails {
+            method: request.method().clone(),
+            path: request.uri().path().to_string(),
+            request_id: request.headers().get(REQUEST_ID_HEADER).cloned(),
+            started: Instant::now(),
+            layer: self.layer,
+        };
+
+        DynErrorTraceFuture {
+            inner: self.inner.call(request),
+            details: Some(details),
+        }
+    }
+}
+
+/// Details about the request captured when the request started
+struct RequestDetails {
+    /// The method of the request
+    method: Method,
+    /// The path of the request
+    path: String,
+    /// The ID of the request if known
+    request_id: Option<HeaderValue>,
+    /// When the request started
+    started: Instant,
+    /// The layer configuration
+    layer: DynErrorTraceLayer,
+}
+
+impl RequestDetails {
+    /// Formats the start of the log line shared by responses and failures
+    fn prefix(&self) -> String {
+        let mut line = format!("{} {}", self.method, self.path);
+        if let Some(request_id) = self
+            .request_id
+            .as_ref()
+            .and_then(|value| value.to_str().ok())
+        {
+            _ = write!(line, " request_id={request_id}");
+        }
+        line
+    }
+
+    /// Logs the line for the provided response
+    fn log_response<B>(&self, response: &Response<B>) {
+        let status = response.status();
+        let level = if status.is_server_error() {
+            self.layer.failure_level
+        } else {
+            self.layer.success_level
+        };
+
+        let mut line = self.prefix();
+        _ = write!(
+            line,
+            " status={} latency_ms={}",
+            status.as_u16(),
+            self.started.elapsed().as_millis()
+        );
+
+        let extensions = response.extensions();
+        if let Some(classification) = extensions.get::<ErrorClassification>() {
+            _ = write!(line, " error_kind={}", classification.kind);
+        }
+        if let Some(identity) = extensions.get::<ErrorIdentity>() {
+            _ = write!(line, " error_type={}", identity.type_name);
+            if let Some(code) = &identity.code {
+                _ = write!(line, " error_code={code}");
+            }
+            _ = write!(line, " fingerprint={:016x}", identity.fingerprint);
+        }
+
+        log::log!(level, "{line}");
+    }
+
+    /// Logs the line for a failure of the inner service
+    fn log_failure(&self) {
+        log::log!(
+            self.layer.failure_level,
+            "{} failed latency_ms={}",
+            self.prefix(),
+            self.started.elapsed().as_millis()
+        );
+    }
+}
+
+pin_project! {
+    /// Future returned by [DynErrorTraceService]
+    pub struct DynErrorTraceFuture<F> {
+        #[pin]
+        inner: F,
+        details: Option<RequestDetails>,
+    }
+}
+
+impl<F, ResBody, E> Future for DynErrorTraceFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = match this.inner.poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        if let Some(details) = this.details.take() {
+            match &result {
+                Ok(response) => details.log_response(response),
+                Err(_) => details.log_failure(),
+            }
+        }
+
+        Poll::Ready(result)
+    }
+}
@@ -0,0 +1,242 @@
+//! Layer for counting transient failures per route for circuit
+//! breaking and load shedding decisions
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, RwLock};
+use std::task::{Context, Poll};
+
+use axum::extract::MatchedPath;
+use http::{Extensions, Request, Response, Uri};
+use pin_project_lite::pin_project;
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::ErrorClassification;
+
+/// Route key used for requests that didn't match a route
+pub const UNMATCHED_ROUTE: &str = "<unmatched>";
+
+/// Route key used for failures of new routes once the maximum number of
+/// routes is being tracked
+pub const OVERFLOW_ROUTE: &str = "<overflow>";
+
+/// Function for determining the route key of a request
+type RouteKey = Arc<dyn Fn(&Uri, &Extensions) -> String + Send + Sync>;
+
+/// Transient failure counts for a single route
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RouteFailures {
+    /// Number of transient failures since the last response that
+    /// wasn't a transient failure
+    pub consecutive: u64,
+    /// Total number of transient failures
+    pub total: u64,
+}
+
+/// Shared handle for reading the transient failure counts recorded by
+/// a [TransientFailureLayer], clones of the handle share the same state
+///
+/// At most 1024 routes are tracked by default (See [TransientFailures::max_routes]),
+/// failures of other routes are counted under [OVERFLOW_ROUTE]
+#[derive(Debug, Clone)]
+pub struct TransientFailures {
+    /// Failure counts for each route
+    routes: Arc<RwLock<HashMap<String, RouteFailures>>>,
+    /// Maximum number of routes tracked
+    max_routes: usize,
+}
+
+impl Default for TransientFailures {
+    fn default() -> Self {
+        Self {
+            routes: Default::default(),
+            max_routes: 1024,
+        }
+    }
+}
+
+impl TransientFailures {
+    /// Creates a new handle without any recorded failures
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum number of routes tracked, once reached failures
+    /// of routes that aren't already tracked are counted under [OVERFLOW_ROUTE]
+    pub fn max_routes(mut self, max_routes: usize) -> Self {
+        self.max_routes = max_routes;
+        self
+    }
+
+    /// Obtains the failure counts for the provided route
+    pub fn get(&self, route: &str) -> RouteFailures {
+        let routes = self.routes.read().unwrap_or_else(|err| err.into_inner());
+        routes.get(route).copied().unwrap_or_default()
+    }
+
+    /// Obtains the failure counts for every route that has failed
+    pub fn routes(&self) -> Vec<(String, RouteFailures)> {
+        let routes = self.routes.read().unwrap_or_else(|err| err.into_inner());
+        routes
+            .iter()
+            .map(|(route, failures)| (route.clone(), *failures))
+            .collect()
+    }
+
+    /// Resets the failure counts for the provided route
+    pub fn reset(&self, route: &str) {
+        let mut routes = self.routes.write().unwrap_or_else(|err| err.into_inner());
+        routes.remove(route);
+    }
+
+    /// Records the outcome of a request to the provided route
+    fn record(&self, route: String, transient: bool) {
+        if !transient {
+            // Avoid taking the write lock for routes that haven't failed
+            let routes = self.routes.read().unwrap_or_else(|err| err.into_inner());
+            if routes
+                .get(&route)
+                .is_none_or(|failures| failures.consecutive == 0)
+            {
+                return;
+            }
+        }
+
+        let mut routes = self.routes.write().unwrap_or_else(|err| err.into_inner());
+        let route = if routes.len() >= self.max_routes && !routes.contains_key(&route) {
+            OVERFLOW_ROUTE.to_string()
+        } else {
+            route
+        };
+        let failures = routes.entry(route).or_default();
+        if transient {
+            failures.consecutive += 1;
+            failures.total += 1;
+        } else {
+            failures.consecutive = 0;
+        }
+    }
+}
+
+/// Layer that counts transient failures (See [HttpError::is_transient](crate::HttpError::is_transient))
+/// for each route using the [ErrorClassification] of error responses.
+/// Errors returned by the inner service are also counted as transient failures
+///
+/// Routes are keyed by axum's [MatchedPath] by default so that requests to
+/// the same route with different parameters share their counts, requests
+/// that didn't match a route are counted under [UNMATCHED_ROUTE]. Use
+/// [TransientFailureLayer::route_key] to group requests differently (i.e
+/// using the `MatchedPath` of axum 0.7 or 0.8)
+#[derive(Clone)]
+pub struct TransientFailureLayer {
+    /// The shared failure counts
+    failures: TransientFailures,
+    /// Function for determining the route key of a request
+    route_key: RouteKey,
+}
+
+impl TransientFailureLayer {
+    /// Creates a new layer recording failures into the provided handle
+    pub fn new(failures: TransientFailures) -> Self {
+        Self {
+            failures,
+            route_key: Arc::new(|_, extensions| match extensions.get::<MatchedPath>() {
+                Some(path) => path.as_str().to_string(),
+                None => UNMATCHED_ROUTE.to_string(),
+            }),
+        }
+    }
+
+    /// Sets the function used to determine the route key from the
+    /// request URI and extensions
+    pub fn route_key<F>(mut self, route_key: F) -> Self
+    where
+        F: Fn(&Uri, &Extensions) -> String + Send + Sync + 'static,
+    {
+        self.route_key = Arc::new(route_key);
+        self
+    }
+}
+
+impl<S> Layer<S> for TransientFailureLayer {
+    type Service = TransientFailureService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TransientFailureService {
+            inner,
+            failures: self.failures.clone(),
+            route_key: self.route_key.clone(),
+        }
+    }
+}
+
+/// Service created by [TransientFailureLayer]
+#[derive(Clone)]
+pub struct TransientFailureService<S> {
+    /// The inner service
+    inner: S,
+    /// The shared failure counts
+    failures: TransientFailures,
+    /// Function for determining the route key of a request
+    route_key: RouteKey,
+}
+
+impl<S, B, ResBody> Service<Request<B>> for TransientFailureService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = TransientFailureFuture<S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let route = (self.route_key)(request.uri(), request.extensions());
+        TransientFailureFuture {
+            inner: self.inner.call(request),
+            record: Some((self.failures.clone(), route)),
+        }
+    }
+}
+
+pin_project! {
+    /// Future returned by [TransientFailureService]
+    pub struct TransientFailureFuture<F> {
+        #[pin]
+        inner: F,
+        record: Option<(TransientFailures, String)>,
+    }
+}
+
+impl<F, ResBody, E> Future for TransientFailureFuture<F>
+where
+    F: Future<Output = Result<Response<ResBody>, E>>,
+{
+    type Output = F::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let result = match this.inner.poll(cx) {
+            Poll::Ready(result) => result,
+            Poll::Pending => return Poll::Pending,
+        };
+
+        if let Some((failures, route)) = this.record.take() {
+            let transient = match &result {
+                Ok(response) => response
+                    .extensions()
+                    .get::<ErrorClassification>()
+                    .is_some_and(|classification| classification.transient),
+                Err(_) => true,
+            };
+            failures.record(route, transient);
+        }
+
+        Poll::Ready(result)
+    }
+}
@@ -0,0 +1,108 @@
+//! Formatter for Hotwire front ends rendering errors as Turbo Stream
+//! fragments for requests that accept them
+
+use std::marker::PhantomData;
+
+use axum_core::response::{IntoResponse, Response};
+use http::{
+    header::{ACCEPT, CONTENT_TYPE},
+    request::Parts,
+    HeaderValue,
+};
+
+use crate::html::escape_html;
+use crate::negotiate::quality;
+use crate::{HttpError, IntoHttpErrorResponse, Negotiated};
+
+/// Media type of Turbo Stream responses
+pub const TURBO_STREAM: &str = "text/vnd.turbo-stream.html";
+
+/// Rules used by the [TurboStreamErrorResponse] formatter
+pub trait TurboStreamRules {
+    /// ID of the element the stream action targets
+    const TARGET: &'static str = "errors";
+
+    /// The stream action to perform on the target (i.e `update`, `replace`
+    /// or `append`)
+    const ACTION: &'static str = "update";
+
+    /// Creates the HTML placed in the `<template>` of the stream action
+    fn template(error: &dyn HttpError) -> String {
+        format!(
+            "<div class=\"error\" role=\"alert\">{}</div>",
+            escape_html(&error.reason())
+        )
+    }
+}
+
+/// [TurboStreamRules] updating the `errors` element with a minimal fragment
+pub struct DefaultTurboStream;
+
+impl TurboStreamRules for DefaultTurboStream {}
+
+/// Formatter that responds with a `text/vnd.turbo-stream.html` stream action
+/// from the rules `R` when the `Accept` header of the request explicitly
+/// accepts Turbo Streams (i.e Turbo form submissions), other requests use
+/// the fallback formatter `F` which by default negotiates between a full
+/// HTML page and JSON
+///
+/// Requires the request parts to be captured by the `RequestContextLayer`,
+/// without the request parts the fallback formatter is always used
+///
+/// ```
+/// use axum_dyn_error::{HttpResult, TurboStreamErrorResponse, TurboStreamRules};
+///
+/// pub struct FlashErrors;
+///
+/// impl TurboStreamRules for FlashErrors {
+///     const TARGET: &'static str = "flash";
+///     const ACTION: &'static str = "append";
+/// }
+///
+/// pub type MyHttpResult<T> = HttpResult<T, TurboStreamErrorResponse<FlashErrors>>;
+/// ```
+pub struct TurboStreamErrorResponse<R = DefaultTurboStream, F = Negotiated> {
+    /// Marker for storing the rules and fallback formatter types
+    _marker: PhantomData<fn() -> (R, F)>,
+}
+
+impl<R, F> IntoHttpErrorResponse for TurboStreamErrorResponse<R, F>
+where
+    R: TurboStreamRules,
+    F: IntoHttpErrorResponse,
+{
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        F::into_response(error)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        let accepts_stream = parts
+            .and_then(|parts| parts.headers.get(ACCEPT))
+            .and_then(|value| value.to_str().ok())
+            .is_some_and(accepts_turbo_stream);
+
+        if !accepts_stream {
+            return F::into_response_with_parts(error, parts);
+        }
+
+        let stream = format!(
+            "<turbo-stream action=\"{}\" target=\"{}\"><template>{}</template></turbo-stream>",
+            escape_html(R::ACTION),
+            escape_html(R::TARGET),
+            R::template(error.as_ref())
+        );
+
+        let mut response = (error.status(), stream).into_response();
+        response.headers_mut().insert(
+            CONTENT_TYPE,
+            HeaderValue::from_static("text/vnd.turbo-stream.html; charset=utf-8"),
+        );
+        response
+    }
+}
+
+/// Checks whether the `Accept` header value explicitly accepts Turbo
+/// Streams, wildcards alone don't select Turbo Streams
+fn accepts_turbo_stream(accept: &str) -> bool {
+    accept.to_ascii_lowercase().contains(TURBO_STREAM) && quality(accept, TURBO_STREAM) > 0.0
+}
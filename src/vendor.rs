@@ -0,0 +1,109 @@
+//! Error responses using vendor specific media types with versioned schemas
+//! (i.e `application/vnd.mycompany.error+json; version=2`)
+
+use std::marker::PhantomData;
+
+use axum_core::response::{IntoResponse, Response};
+use http::{
+    header::{ACCEPT, CONTENT_TYPE},
+    request::Parts,
+    HeaderValue,
+};
+use serde_json::Value;
+
+use crate::{HttpError, IntoHttpErrorResponse};
+
+/// Vendor specific error schema used by the [VendorJsonResponse] formatter
+pub trait VendorSchema {
+    /// The vendor media type without any parameters
+    /// (i.e `application/vnd.mycompany.error+json`)
+    const MEDIA_TYPE: &'static str;
+
+    /// Version of the schema used when the request doesn't ask for a
+    /// specific version or asks for a version that isn't supported
+    const DEFAULT_VERSION: u32;
+
+    /// Creates the body for the error using the schema of the provided
+    /// version, returns [None] if the version isn't supported
+    fn body(error: &dyn HttpError, version: u32) -> Option<Value>;
+}
+
+/// Formatter that creates JSON error responses using the schema and media
+/// type from `S`, the schema version is taken from the `version` parameter
+/// of the vendor media type in the `Accept` header of the request (Requires
+/// the request parts to be captured by the `RequestContextLayer`)
+///
+/// ```
+/// use axum_dyn_error::{HttpError, HttpResult, VendorJsonResponse, VendorSchema};
+/// use serde_json::{json, Value};
+///
+/// pub struct MyErrorSchema;
+///
+/// impl VendorSchema for MyErrorSchema {
+///     const MEDIA_TYPE: &'static str = "application/vnd.mycompany.error+json";
+///     const DEFAULT_VERSION: u32 = 2;
+///
+///     fn body(error: &dyn HttpError, version: u32) -> Option<Value> {
+///         match version {
+///             1 => Some(json!({ "message": error.reason() })),
+///             2 => Some(json!({ "error": { "status": error.status().as_u16(), "message": error.reason() } })),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// pub type MyHttpResult<T> = HttpResult<T, VendorJsonResponse<MyErrorSchema>>;
+/// ```
+pub struct VendorJsonResponse<S> {
+    /// Marker for storing the schema type
+    _marker: PhantomData<fn() -> S>,
+}
+
+impl<S: VendorSchema> IntoHttpErrorResponse for VendorJsonResponse<S> {
+    fn into_response(error: Box<dyn HttpError>) -> Response {
+        Self::into_response_with_parts(error, None)
+    }
+
+    fn into_response_with_parts(error: Box<dyn HttpError>, parts: Option<&Parts>) -> Response {
+        let requested = parts
+            .and_then(|parts| parts.headers.get(ACCEPT))
+            .and_then(|value| value.to_str().ok())
+            .and_then(|accept| requested_version(accept, S::MEDIA_TYPE));
+
+        let (version, body) = match requested
+            .and_then(|version| S::body(error.as_ref(), version).map(|body| (version, body)))
+        {
+            Some(versioned) => versioned,
+            None => (
+                S::DEFAULT_VERSION,
+                S::body(error.as_ref(), S::DEFAULT_VERSION).unwrap_or(Value::Null),
+            ),
+        };
+
+        let Ok(body) = serde_json::to_vec(&body) else {
+            return http::StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        };
+
+        let mut response = (error.status(), body).into_response();
+        if let Ok(content_type) =
+            HeaderValue::from_str(&format!("{}; version={version}", S::MEDIA_TYPE))
+        {
+            response.headers_mut().insert(CONTENT_TYPE, content_type);
+        }
+        response
+    }
+}
+
+/// Finds the `version` parameter of the media type in the `Accept` header value
+fn requested_version(accept: &str, media_type: &str) -> Option<u32> {
+    accept.split(',').find_map(|range| {
+        let mut params = range.split(';');
+        if !params.next()?.trim().eq_ignore_ascii_case(media_type) {
+            return None;
+        }
+
+        params
+            .find_map(|param| param.trim().strip_prefix("version="))
+            .and_then(|version| version.trim_matches('"').parse().ok())
+    })
+}
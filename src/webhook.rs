@@ -0,0 +1,220 @@
+//! Alerting on critical errors by posting batches of error events to a
+//! webhook (i.e Slack or PagerDuty style incoming webhooks)
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use http::StatusCode;
+use serde_json::{json, Value};
+use tokio::sync::mpsc::Receiver;
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout, Instant};
+
+use crate::{ChannelErrorSink, ErrorEvent, ErrorSink, ErrorSinks};
+
+/// Function for creating the webhook payload for a batch of events
+type PayloadFn = Arc<dyn Fn(&[ErrorEvent]) -> Value + Send + Sync>;
+
+/// Posts a JSON payload to a webhook for errors at or above a status
+/// threshold, events are batched and failed requests are retried with
+/// exponential backoff
+///
+/// Alerting happens in a background task so the alerter must be spawned
+/// from within a tokio runtime
+///
+/// ```no_run
+/// use axum_dyn_error::{ErrorEvent, StatusCode, WebhookAlerter};
+/// use serde_json::json;
+///
+/// # async fn example() {
+/// WebhookAlerter::new("https://hooks.slack.com/services/...")
+///     .min_status(StatusCode::INTERNAL_SERVER_ERROR)
+///     .payload(|events: &[ErrorEvent]| {
+///         let lines: Vec<String> = events
+///             .iter()
+///             .map(|event| format!("{} {}", event.status, event.message))
+///             .collect();
+///         json!({ "text": lines.join("\n") })
+///     })
+///     .spawn();
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct WebhookAlerter {
+    /// URL of the webhook
+    url: String,
+    /// Minimum status of the errors to alert on
+    min_status: StatusCode,
+    /// Maximum number of events sent in a single request
+    batch_size: usize,
+    /// Maximum time to wait for a batch to fill before sending it
+    batch_interval: Duration,
+    /// Maximum number of times a failed request is retried
+    max_retries: u32,
+    /// Delay before the first retry, doubled after each retry
+    retry_backoff: Duration,
+    /// Maximum time a single request may take
+    request_timeout: Duration,
+    /// Number of events buffered before new events are dropped
+    capacity: usize,
+    /// Function for creating the payload
+    payload: PayloadFn,
+}
+
+impl WebhookAlerter {
+    /// Creates a new alerter for the provided webhook URL, by default
+    /// server errors are sent in batches of up to 10 events every 5
+    /// seconds as `{"errors": [...]}` retrying up to 3 times, requests
+    /// time out after 10 seconds
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            min_status: StatusCode::INTERNAL_SERVER_ERROR,
+            batch_size: 10,
+            batch_interval: Duration::from_secs(5),
+            max_retries: 3,
+            retry_backoff: Duration::from_secs(1),
+            request_timeout: Duration::from_secs(10),
+            capacity: 1024,
+            payload: Arc::new(|events| json!({ "errors": events })),
+        }
+    }
+
+    /// Sets the minimum status of the errors to alert on
+    pub fn min_status(mut self, status: StatusCode) -> Self {
+        self.min_status = status;
+        self
+    }
+
+    /// Sets the maximum number of events sent in a single request
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Sets the maximum time to wait for a batch to fill before sending it
+    pub fn batch_interval(mut self, batch_interval: Duration) -> Self {
+        self.batch_interval = batch_interval;
+        self
+    }
+
+    /// Sets the maximum number of times a failed request is retried
+    pub fn max_retries(mut self, max_retries: u32) -> Self {
+        self.max_retries = max_retries;
+        self
+    }
+
+    /// Sets the delay before the first retry, the delay is doubled
+    /// after each retry
+    pub fn retry_backoff(mut self, retry_backoff: Duration) -> Self {
+        self.retry_backoff = retry_backoff;
+        self
+    }
+
+    /// Sets the maximum time a single request may take before it fails
+    /// and is retried, so a hung webhook doesn't block later alerts
+    pub fn request_timeout(mut self, request_timeout: Duration) -> Self {
+        self.request_timeout = request_timeout;
+        self
+    }
+
+    /// Sets the number of events buffered before new events are dropped
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+
+    /// Sets the function for creating the JSON payload for a batch of events
+    pub fn payload<F>(mut self, payload: F) -> Self
+    where
+        F: Fn(&[ErrorEvent]) -> Value + Send + Sync + 'static,
+    {
+        self.payload = Arc::new(payload);
+        self
+    }
+
+    /// Registers the alerter as an [ErrorSink] and spawns the background
+    /// task that sends the alerts, the task runs until the sink is
+    /// removed using [ErrorSinks::clear]
+    pub fn spawn(self) -> JoinHandle<()> {
+        let (sink, receiver) = ChannelErrorSink::new(self.capacity);
+        let min_status = self.min_status;
+
+        ErrorSinks::register(move |event: &ErrorEvent| {
+            if event.status >= min_status {
+                sink.send(event);
+            }
+        });
+
+        tokio::spawn(self.run(receiver))
+    }
+
+    /// Collects the events into batches and sends them until the channel
+    /// is closed
+    async fn run(self, mut receiver: Receiver<ErrorEvent>) {
+        let client = reqwest::Client::new();
+
+        while let Some(event) = receiver.recv().await {
+            let mut batch = vec![event];
+            let deadline = Instant::now() + self.batch_interval;
+
+            while batch.len() < self.batch_size {
+                let remaining = deadline.saturating_duration_since(Instant::now());
+                match timeout(remaining, receiver.recv()).await {
+                    Ok(Some(event)) => batch.push(event),
+                    Ok(None) | Err(_) => break,
+                }
+            }
+
+            self.send(&client, &batch).await;
+        }
+    }
+
+    /// Sends a batch of events retrying failed requests
+    async fn send(&self, client: &reqwest::Client, batch: &[ErrorEvent]) {
+        let payload = (self.payload)(batch);
+        let Ok(body) = serde_json::to_vec(&payload) else {
+            return;
+        };
+
+        let mut backoff = self.retry_backoff;
+        for attempt in 0..=self.max_retries {
+            if attempt > 0 {
+                sleep(backoff).await;
+                backoff = backoff.saturating_mul(2);
+            }
+
+            let result = client
+                .post(&self.url)
+                .timeout(self.request_timeout)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(body.clone())
+                .send()
+                .await;
+
+            match result {
+                Ok(response) if response.status().is_success() => return,
+                // Client errors other than rate limiting won't succeed on retry
+                Ok(response)
+                    if response.status().is_client_error()
+                        && response.status() != reqwest::StatusCode::TOO_MANY_REQUESTS =>
+                {
+                    break
+                }
+                _ => {}
+            }
+        }
+
+        // Only the host is logged as the URL of webhooks is often a secret
+        #[cfg(feature = "log")]
+        log::warn!(
+            "failed to send {} error alert(s) to webhook host {}",
+            batch.len(),
+            reqwest::Url::parse(&self.url)
+                .ok()
+                .as_ref()
+                .and_then(reqwest::Url::host_str)
+                .unwrap_or("<invalid url>")
+        );
+    }
+}